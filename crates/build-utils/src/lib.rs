@@ -46,11 +46,35 @@ pub fn detect_self_crate_version() -> String {
     // `root_package` returns the crate of the `build.rs` that being ran.
     let version = meta.root_package().unwrap().version.to_string();
 
-    let output = std::process::Command::new("git")
-        .args(["rev-parse", "--short=7", "HEAD"])
-        .output()
-        .expect("Failed to get git revision");
-    let rev = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let rev = git(&["rev-parse", "--short=7", "HEAD"]).unwrap_or_default();
+
+    // Rerun when the revision changes, i.e. `HEAD` is moved or the branch it
+    // points to is updated. Only existing files are watched, since a missing
+    // one makes cargo rerun every time.
+    let head_ref = git(&["symbolic-ref", "-q", "HEAD"]);
+    for name in ["HEAD", "packed-refs"]
+        .into_iter()
+        .chain(head_ref.as_deref())
+    {
+        if let Some(path) = git(&["rev-parse", "--git-path", name]) {
+            if Path::new(&path).exists() {
+                println!("cargo:rerun-if-changed={path}");
+            }
+        }
+    }
 
     format!("{version}-{rev}")
 }
+
+/// Runs `git` with `args`, returns the trimmed stdout, or `None` if it fails
+/// (e.g. not in a git repository).
+fn git(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .expect("Failed to run git");
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
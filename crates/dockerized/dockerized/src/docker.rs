@@ -1,48 +1,35 @@
 use crate::error::Error;
+use serde_json::{Map, Value, json};
 use std::{
+    collections::{BTreeMap, VecDeque},
     env,
-    fmt::{self, Display, Formatter},
-    io::{self, Write},
-    path::Path,
-    process::{Child, Command, Stdio},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Mutex,
+    thread,
+    time::Duration,
 };
+use tracing::{error, info};
 
-pub const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+mod engine;
 
-#[derive(Clone)]
-pub struct CmdOption(String, Option<String>);
+pub use engine::{ContainerLogs, DockerEngine, DockerError, LogStream};
 
-impl CmdOption {
-    pub fn new(key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        Self(to_string(key), Some(to_string(value)))
-    }
+pub const DOCKER_SOCKET: &str = "/var/run/docker.sock";
 
-    pub fn flag(key: impl AsRef<str>) -> Self {
-        Self(to_string(key), None)
-    }
+/// Time to wait for the container to stop before killing it, same as the
+/// default of `docker stop`.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
 
-    pub fn to_args(&self) -> Vec<String> {
-        let Self(key, value) = self;
-        match value {
-            Some(value) => vec![format!("--{key}"), format!("{value}")],
-            None => vec![format!("--{key}")],
-        }
-    }
-}
-
-impl Display for CmdOption {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Self(key, value) = self;
-        match value {
-            Some(value) => write!(f, "--{key} {value}"),
-            None => write!(f, "--{key}"),
-        }
-    }
-}
+/// Number of the last `docker build` output lines kept for the error.
+const BUILD_OUTPUT_TAIL_LINES: usize = 20;
 
 #[derive(Default)]
 pub struct DockerBuildCmd {
-    options: Vec<CmdOption>,
+    file: Option<PathBuf>,
+    tags: Vec<String>,
+    build_args: BTreeMap<String, String>,
 }
 
 impl DockerBuildCmd {
@@ -50,106 +37,195 @@ impl DockerBuildCmd {
         Self::default()
     }
 
-    pub fn option(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        self.options.push(CmdOption::new(key, value));
+    pub fn file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(file.as_ref().to_path_buf());
         self
     }
 
-    pub fn file(self, file: impl AsRef<Path>) -> Self {
-        self.option("file", file.as_ref().to_string_lossy())
-    }
-
-    pub fn tag(self, tag: impl AsRef<str>) -> Self {
-        self.option("tag", tag)
+    pub fn tag(mut self, tag: impl AsRef<str>) -> Self {
+        self.tags.push(to_string(tag));
+        self
     }
 
-    pub fn build_arg(self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        self.option(
-            "build-arg",
-            format!("{}={}", to_string(key), to_string(value)),
-        )
+    pub fn build_arg(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.build_args.insert(to_string(key), to_string(value));
+        self
     }
 
-    pub fn exec(self, context: impl AsRef<Path>) -> Result<(), io::Error> {
+    /// Builds the image with `context` directory by `docker build` with
+    /// BuildKit, which is required by the Dockerfiles (e.g. `COPY --chmod`)
+    /// and handles `.dockerignore` and streaming of the context.
+    ///
+    /// The build output is logged line by line, and the last lines are
+    /// included in [`DockerError::Build`] if the build fails.
+    pub fn exec(self, context: impl AsRef<Path>) -> Result<(), Error> {
         let mut cmd = Command::new("docker");
-        cmd.arg("build");
-        for option in self.options {
-            cmd.args(option.to_args());
+        cmd.arg("build")
+            .env("DOCKER_BUILDKIT", "1")
+            .args(["--progress", "plain"]);
+        if let Some(file) = &self.file {
+            cmd.arg("--file").arg(file);
         }
-        cmd.arg(context.as_ref().to_string_lossy().to_string());
-
-        let status = cmd.status()?;
+        for tag in &self.tags {
+            cmd.args(["--tag", tag]);
+        }
+        for (key, value) in &self.build_args {
+            cmd.arg("--build-arg").arg(format!("{key}={value}"));
+        }
+        cmd.arg(context.as_ref())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = cmd.spawn().map_err(DockerError::BuildCmd)?;
+        let tail = Mutex::new(VecDeque::with_capacity(BUILD_OUTPUT_TAIL_LINES));
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        thread::scope(|scope| {
+            scope.spawn(|| log_build_output(stdout, &tail));
+            log_build_output(stderr, &tail);
+        });
+        let status = child.wait().map_err(DockerError::BuildCmd)?;
 
         if !status.success() {
-            return Err(io::Error::other(format!(
-                "Command {cmd:?} failed with status: {status}",
-            )));
+            let tail = Vec::from(tail.into_inner().unwrap()).join("\n");
+            return Err(DockerError::Build {
+                tag: self.tags.first().cloned().unwrap_or_default(),
+                message: format!("`docker build` failed with {status}, last output:\n{tail}"),
+            })?;
         }
 
         Ok(())
     }
 }
 
+/// Logs the `docker build` output line by line, and keeps the last lines in
+/// `tail`.
+fn log_build_output(output: impl Read, tail: &Mutex<VecDeque<String>>) {
+    for line in BufReader::new(output).lines().map_while(Result::ok) {
+        info!("{line}");
+        let mut tail = tail.lock().unwrap();
+        if tail.len() == BUILD_OUTPUT_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+}
+
 pub struct DockerRunCmd {
-    options: Vec<CmdOption>,
     image: String,
+    name: Option<String>,
+    envs: Vec<String>,
+    binds: Vec<String>,
+    /// Pairs of host and container ports.
+    ports: Vec<(String, String)>,
+    labels: BTreeMap<String, String>,
+    gpus: Option<String>,
+    network: Option<String>,
+    shm_size: Option<String>,
+    /// Tuples of ulimit name, soft and hard limits.
+    ulimits: Vec<(String, i64, i64)>,
+    cpus: Option<f64>,
+    cpuset_cpus: Option<String>,
+    memory: Option<String>,
+    mount_docker_socket: bool,
+    rm: bool,
 }
 
 impl DockerRunCmd {
     pub fn new(image: String) -> Self {
         Self {
-            options: Vec::new(),
             image,
+            name: None,
+            envs: Vec::new(),
+            binds: Vec::new(),
+            ports: Vec::new(),
+            labels: BTreeMap::new(),
+            gpus: None,
+            network: None,
+            shm_size: None,
+            ulimits: Vec::new(),
+            cpus: None,
+            cpuset_cpus: None,
+            memory: None,
+            mount_docker_socket: false,
+            rm: false,
         }
     }
 
-    pub fn flag(mut self, key: impl AsRef<str>) -> Self {
-        self.options.push(CmdOption::flag(key));
+    /// Publishes `container` port on `host` port, or on an ephemeral port if
+    /// `host` is empty, which can be looked up by [`docker_host_port`].
+    pub fn publish(mut self, host: impl AsRef<str>, container: impl AsRef<str>) -> Self {
+        self.ports.push((to_string(host), to_string(container)));
+        self
+    }
+
+    pub fn volume(mut self, host: impl AsRef<Path>, container: impl AsRef<Path>) -> Self {
+        self.binds.push(format!(
+            "{}:{}",
+            host.as_ref().display(),
+            container.as_ref().display(),
+        ));
         self
     }
 
-    pub fn option(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        self.options.push(CmdOption::new(key, value));
+    pub fn env(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.envs
+            .push(format!("{}={}", key.as_ref(), value.as_ref()));
         self
     }
 
-    pub fn publish(self, host: impl AsRef<str>, container: impl AsRef<str>) -> Self {
-        self.option(
-            "publish",
-            format!("{}:{}", host.as_ref(), container.as_ref()),
-        )
+    /// Mounts the socket of the Docker daemon (see [`DockerEngine::from_env`])
+    /// to `/var/run/docker.sock` to allow Docker-out-of-Docker (DooD).
+    pub fn mount_docker_socket(mut self) -> Self {
+        self.mount_docker_socket = true;
+        self
     }
 
-    pub fn volume(self, host: impl AsRef<Path>, container: impl AsRef<Path>) -> Self {
-        self.option(
-            "volume",
-            format!(
-                "{}:{}",
-                host.as_ref().display(),
-                container.as_ref().display(),
-            ),
-        )
+    /// Requests GPU devices, either `all` or comma-separated device ids.
+    pub fn gpus(mut self, devices: impl AsRef<str>) -> Self {
+        self.gpus = Some(to_string(devices));
+        self
     }
 
-    pub fn env(self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
-        self.option("env", format!("{}={}", key.as_ref(), value.as_ref()))
+    pub fn network(mut self, name: impl AsRef<str>) -> Self {
+        self.network = Some(to_string(name));
+        self
     }
 
-    /// Mounts `/var/run/docker.sock` to allow Docker-out-of-Docker (DooD).
-    pub fn mount_docker_socket(self) -> Self {
-        self.volume(DOCKER_SOCKET, DOCKER_SOCKET)
+    pub fn name(mut self, name: impl AsRef<str>) -> Self {
+        self.name = Some(to_string(name));
+        self
+    }
+
+    pub fn label(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.labels.insert(to_string(key), to_string(value));
+        self
+    }
+
+    pub fn shm_size(mut self, size: impl AsRef<str>) -> Self {
+        self.shm_size = Some(to_string(size));
+        self
+    }
+
+    pub fn ulimit(mut self, name: impl AsRef<str>, soft: i64, hard: i64) -> Self {
+        self.ulimits.push((to_string(name), soft, hard));
+        self
     }
 
-    pub fn gpus(self, devices: impl AsRef<str>) -> Self {
-        self.option("gpus", devices)
+    pub fn cpus(mut self, cpus: f64) -> Self {
+        self.cpus = Some(cpus);
+        self
     }
 
-    pub fn network(self, name: impl AsRef<str>) -> Self {
-        self.option("network", name)
+    pub fn cpuset_cpus(mut self, cpus: impl AsRef<str>) -> Self {
+        self.cpuset_cpus = Some(to_string(cpus));
+        self
     }
 
-    pub fn name(self, name: impl AsRef<str>) -> Self {
-        self.option("name", name)
+    pub fn memory(mut self, limit: impl AsRef<str>) -> Self {
+        self.memory = Some(to_string(limit));
+        self
     }
 
     /// Inherit environment variable `key` if it's set and valid.
@@ -161,83 +237,237 @@ impl DockerRunCmd {
         }
     }
 
-    pub fn rm(self) -> Self {
-        self.flag("rm")
+    pub fn rm(mut self) -> Self {
+        self.rm = true;
+        self
     }
 
+    /// Creates and starts the container, writes `stdin` to it, and returns
+    /// its output until it stops.
     pub fn spawn(
-        mut self,
+        self,
         commands: impl IntoIterator<Item: AsRef<str>>,
         stdin: &[u8],
-    ) -> Result<Child, io::Error> {
-        self = self.flag("interactive");
+    ) -> Result<ContainerLogs, Error> {
+        let engine = DockerEngine::from_env()?;
+        let config = self.config(&engine, commands, true)?;
+        let id = engine.create_container(self.name.as_deref().unwrap_or_default(), &config)?;
 
-        let mut cmd = Command::new("docker");
-        cmd.arg("run");
-        for option in self.options {
-            cmd.args(option.to_args());
-        }
-        cmd.arg(self.image);
-        for command in commands {
-            cmd.arg(command.as_ref());
-        }
+        let container_stdin = engine.attach_stdin(&id)?;
+        engine.start_container(&id)?;
+        container_stdin.write_all_and_close(stdin)?;
 
-        let mut child = cmd.stdin(Stdio::piped()).spawn()?;
+        Ok(engine.logs(&id, true)?)
+    }
 
-        // Write all to stdin then drop to close the pipe.
-        child.stdin.take().unwrap().write_all(stdin)?;
+    /// Runs the container until it exits, and returns
+    /// [`DockerError::ContainerExited`] if it exits with non-zero status.
+    pub fn exec(self, commands: impl IntoIterator<Item: AsRef<str>>) -> Result<(), Error> {
+        self.run(commands, |stream, data| match stream {
+            LogStream::Stdout => drop(io::stdout().write_all(data)),
+            LogStream::Stderr => drop(io::stderr().write_all(data)),
+        })
+    }
 
-        Ok(child)
+    /// Same as [`DockerRunCmd::exec`] but returns the stdout of the container
+    /// instead of writing it to stdout of the current process.
+    pub fn output(self, commands: impl IntoIterator<Item: AsRef<str>>) -> Result<Vec<u8>, Error> {
+        let mut stdout = Vec::new();
+        self.run(commands, |stream, data| match stream {
+            LogStream::Stdout => stdout.extend_from_slice(data),
+            LogStream::Stderr => drop(io::stderr().write_all(data)),
+        })?;
+        Ok(stdout)
     }
 
-    pub fn exec(self, commands: impl IntoIterator<Item: AsRef<str>>) -> Result<(), io::Error> {
-        let mut cmd = Command::new("docker");
-        cmd.arg("run");
-        for option in self.options {
-            cmd.args(option.to_args());
+    /// Runs the container until it exits, and passes its output to
+    /// `on_output`.
+    fn run(
+        self,
+        commands: impl IntoIterator<Item: AsRef<str>>,
+        on_output: impl FnMut(LogStream, &[u8]),
+    ) -> Result<(), Error> {
+        let engine = DockerEngine::from_env()?;
+        let config = self.config(&engine, commands, false)?;
+        let name = self.name.clone().unwrap_or(self.image.clone());
+        let id = engine.create_container(self.name.as_deref().unwrap_or_default(), &config)?;
+
+        let result = (|| {
+            engine.start_container(&id)?;
+            forward_logs(engine.logs(&id, true)?, on_output);
+            engine.wait_container(&id)
+        })();
+
+        // Removed here instead of by `AutoRemove`, so the exit status can be
+        // inspected.
+        if self.rm {
+            if let Err(err) = engine.remove_container(&id, true) {
+                error!("Failed to remove container {name}: {err}");
+            }
         }
-        cmd.arg(self.image);
-        for command in commands {
-            cmd.arg(command.as_ref());
+
+        let exit = result?;
+        if exit.status_code != 0 {
+            return Err(DockerError::ContainerExited {
+                name,
+                status_code: exit.status_code,
+                oom_killed: exit.oom_killed,
+                error: exit.error,
+            })?;
         }
 
-        let status = cmd.status()?;
+        Ok(())
+    }
 
-        if !status.success() {
-            return Err(io::Error::other(format!(
-                "Command {cmd:?} failed with status: {status}",
-            )));
+    /// Returns the body of `POST /containers/create`.
+    fn config(
+        &self,
+        engine: &DockerEngine,
+        commands: impl IntoIterator<Item: AsRef<str>>,
+        stdin: bool,
+    ) -> Result<Value, DockerError> {
+        let mut binds = self.binds.clone();
+        if self.mount_docker_socket {
+            binds.push(format!("{}:{DOCKER_SOCKET}", engine.socket().display()));
         }
 
-        Ok(())
+        let mut host_config = Map::new();
+        host_config.insert("Binds".into(), json!(binds));
+        // Removed in `exec` after inspecting the exit status.
+        host_config.insert("AutoRemove".into(), json!(self.rm && stdin));
+
+        let mut exposed_ports = Map::new();
+        let mut port_bindings = Map::new();
+        for (host, container) in &self.ports {
+            let port = format!("{container}/tcp");
+            exposed_ports.insert(port.clone(), json!({}));
+            port_bindings.insert(port, json!([{ "HostPort": host }]));
+        }
+        host_config.insert("PortBindings".into(), port_bindings.into());
+
+        if let Some(gpus) = &self.gpus {
+            let mut request = json!({ "Driver": "", "Capabilities": [["gpu"]] });
+            if gpus == "all" {
+                request["Count"] = json!(-1);
+            } else {
+                request["DeviceIDs"] = json!(gpus.split(',').collect::<Vec<_>>());
+            }
+            host_config.insert("DeviceRequests".into(), json!([request]));
+        }
+        if let Some(network) = &self.network {
+            host_config.insert("NetworkMode".into(), json!(network));
+        }
+        if let Some(shm_size) = &self.shm_size {
+            host_config.insert("ShmSize".into(), json!(parse_size("shm-size", shm_size)?));
+        }
+        if !self.ulimits.is_empty() {
+            let ulimits = self
+                .ulimits
+                .iter()
+                .map(|(name, soft, hard)| json!({ "Name": name, "Soft": soft, "Hard": hard }))
+                .collect::<Vec<_>>();
+            host_config.insert("Ulimits".into(), json!(ulimits));
+        }
+        if let Some(cpus) = self.cpus {
+            host_config.insert("NanoCpus".into(), json!((cpus * 1e9) as i64));
+        }
+        if let Some(cpuset_cpus) = &self.cpuset_cpus {
+            host_config.insert("CpusetCpus".into(), json!(cpuset_cpus));
+        }
+        if let Some(memory) = &self.memory {
+            host_config.insert("Memory".into(), json!(parse_size("memory", memory)?));
+        }
+
+        let cmd = commands.into_iter().map(to_string).collect::<Vec<_>>();
+        Ok(json!({
+            "Image": self.image,
+            "Cmd": cmd,
+            "Env": self.envs,
+            "Labels": self.labels,
+            "ExposedPorts": exposed_ports,
+            "AttachStdin": stdin,
+            "OpenStdin": stdin,
+            "StdinOnce": stdin,
+            "HostConfig": host_config,
+        }))
     }
 }
 
+/// Passes the container output to `on_output` until the container stops.
+fn forward_logs(logs: ContainerLogs, mut on_output: impl FnMut(LogStream, &[u8])) {
+    for chunk in logs {
+        match chunk {
+            Ok((stream, data)) => on_output(stream, &data),
+            Err(err) => {
+                error!("Failed to read container logs: {err}");
+                break;
+            }
+        }
+    }
+}
+
+/// Stops the container, then removes it if it's not removed automatically.
 pub fn stop_docker_container(container_name: impl AsRef<str>) -> Result<(), Error> {
-    let output = Command::new("docker")
-        .args(["container", "stop", container_name.as_ref()])
-        .output()
-        .map_err(Error::DockerContainerCmd)?;
+    let engine = DockerEngine::from_env()?;
+    let container_name = container_name.as_ref();
 
-    if String::from_utf8_lossy(&output.stdout).starts_with("Error") {
-        return Err(Error::DockerContainerCmd(io::Error::other(format!(
-            "Failed to stop container {}",
-            container_name.as_ref()
-        ))));
+    match engine.stop_container(container_name, STOP_TIMEOUT) {
+        Err(err) if err.status() == Some(404) => return Ok(()),
+        result => result?,
+    }
+    match engine.remove_container(container_name, true) {
+        // Already removed, or being removed by `AutoRemove`.
+        Err(err) if matches!(err.status(), Some(404 | 409)) => Ok(()),
+        result => Ok(result?),
     }
+}
 
-    Ok(())
+/// Returns the host port that TCP `port` of the container is published on.
+pub fn docker_host_port(container_name: impl AsRef<str>, port: u16) -> Result<u16, Error> {
+    Ok(DockerEngine::from_env()?.host_port(container_name.as_ref(), port)?)
 }
 
 pub fn docker_image_exists(image: impl AsRef<str>) -> Result<bool, Error> {
-    let output = Command::new("docker")
-        .args(["images", "--quiet", image.as_ref()])
-        .output()
-        .map_err(Error::DockerImageCmd)?;
-    // If image exists, image id will be printed hence stdout will be non-empty.
-    Ok(!output.stdout.is_empty())
+    Ok(DockerEngine::from_env()?.image_exists(image.as_ref())?)
+}
+
+/// Parses size with optional unit `b`, `k`, `m`, `g` or `t` (in 1024 based)
+/// like the `docker` CLI, e.g. `512m` or `16G`.
+fn parse_size(key: &'static str, value: &str) -> Result<i64, DockerError> {
+    let invalid = || DockerError::InvalidOption {
+        key,
+        value: value.to_string(),
+    };
+    let lower = value.trim().to_ascii_lowercase();
+    let lower = lower.strip_suffix('b').unwrap_or(&lower);
+    let (number, multiplier) = match lower.char_indices().last() {
+        Some((idx, 'k')) => (&lower[..idx], 1u64 << 10),
+        Some((idx, 'm')) => (&lower[..idx], 1 << 20),
+        Some((idx, 'g')) => (&lower[..idx], 1 << 30),
+        Some((idx, 't')) => (&lower[..idx], 1 << 40),
+        _ => (lower, 1),
+    };
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    if !number.is_finite() || number < 0.0 {
+        return Err(invalid());
+    }
+    Ok((number * multiplier as f64) as i64)
 }
 
 fn to_string(s: impl AsRef<str>) -> String {
     s.as_ref().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_size;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("memory", "1024").unwrap(), 1024);
+        assert_eq!(parse_size("memory", "512m").unwrap(), 512 << 20);
+        assert_eq!(parse_size("memory", "16G").unwrap(), 16i64 << 30);
+        assert_eq!(parse_size("memory", "1.5gb").unwrap(), 3 << 29);
+        assert!(parse_size("memory", "lots").is_err());
+    }
+}
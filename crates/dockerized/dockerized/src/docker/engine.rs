@@ -0,0 +1,798 @@
+//! Minimal blocking client of the Docker Engine API over Unix socket.
+//!
+//! Only the endpoints needed to run containers are implemented, each request
+//! opens a new connection to the socket. Images are built by `docker build`
+//! instead, since BuildKit over the API (`POST /build?version=2`) reports its
+//! progress as protobuf messages and needs a session for the build context.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::Shutdown,
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::OnceLock,
+    time::Duration,
+};
+use thiserror::Error;
+
+use super::DOCKER_SOCKET;
+
+#[derive(Debug, Error)]
+pub enum DockerError {
+    #[error("Failed to connect to Docker daemon at {socket}: {source}")]
+    Connect {
+        socket: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Docker API request `{method} {path}` failed: {source}")]
+    Io {
+        method: &'static str,
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("Docker API `{method} {path}` returned invalid response: {message}")]
+    InvalidResponse {
+        method: &'static str,
+        path: String,
+        message: String,
+    },
+    #[error("Docker API `{method} {path}` returned {status}: {message}")]
+    Api {
+        method: &'static str,
+        path: String,
+        status: u16,
+        message: String,
+    },
+    #[error("Unsupported Docker host {0}, only `unix://` sockets are supported")]
+    UnsupportedHost(String),
+    #[error("Failed to run `docker build`: {0}")]
+    BuildCmd(#[source] io::Error),
+    #[error("Failed to build image {tag}: {message}")]
+    Build { tag: String, message: String },
+    #[error("Container {name} exited with {}", exit_reason(.status_code, .oom_killed, .error))]
+    ContainerExited {
+        name: String,
+        status_code: i64,
+        oom_killed: bool,
+        error: Option<String>,
+    },
+    #[error("Invalid {key} option: {value}")]
+    InvalidOption { key: &'static str, value: String },
+}
+
+impl DockerError {
+    /// Returns the HTTP status if the daemon responded with an error.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+fn exit_reason(status_code: &i64, oom_killed: &bool, error: &Option<String>) -> String {
+    let mut reason = format!("status code {status_code}");
+    if *oom_killed {
+        reason.push_str(" (OOM killed)");
+    }
+    if let Some(error) = error {
+        write!(reason, ": {error}").unwrap();
+    }
+    reason
+}
+
+/// Stream of a container log chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// Exit status of a container returned by [`DockerEngine::wait_container`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerExit {
+    pub status_code: i64,
+    pub oom_killed: bool,
+    pub error: Option<String>,
+}
+
+/// Client of the Docker Engine API.
+#[derive(Clone, Debug)]
+pub struct DockerEngine {
+    socket: PathBuf,
+}
+
+impl DockerEngine {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    /// Returns client of the daemon the `docker` CLI talks to, which is the
+    /// one in `DOCKER_HOST` if set, otherwise the endpoint of the current
+    /// docker context (e.g. Docker Desktop or rootless), or [`DOCKER_SOCKET`]
+    /// if the CLI is not available.
+    ///
+    /// Returns [`DockerError::UnsupportedHost`] if the endpoint is not a
+    /// `unix://` socket, e.g. `tcp://` or `ssh://`.
+    pub fn from_env() -> Result<Self, DockerError> {
+        // The current context is only looked up once, since it requires
+        // running the CLI.
+        static CONTEXT_HOST: OnceLock<Option<String>> = OnceLock::new();
+
+        let host = match env::var("DOCKER_HOST") {
+            Ok(host) if !host.is_empty() => host,
+            _ => match CONTEXT_HOST.get_or_init(context_host) {
+                Some(host) => host.clone(),
+                None => return Ok(Self::new(DOCKER_SOCKET)),
+            },
+        };
+        Ok(Self::new(socket_of(&host)?))
+    }
+
+    /// Returns path of the socket.
+    pub fn socket(&self) -> &Path {
+        &self.socket
+    }
+
+    pub fn image_exists(&self, image: &str) -> Result<bool, DockerError> {
+        let path = format!("/images/{}/json", encode(image));
+        let response = self.request("GET", &path, &[], None)?;
+        match response.status {
+            404 => Ok(false),
+            _ => response.ok().map(|_| true),
+        }
+    }
+
+    /// Creates container with `config` as the body of `POST /containers/create`,
+    /// and returns its id.
+    pub fn create_container(&self, name: &str, config: &Value) -> Result<String, DockerError> {
+        #[derive(Deserialize)]
+        struct CreateResponse {
+            #[serde(rename = "Id")]
+            id: String,
+        }
+
+        let path = format!("/containers/create?name={}", encode(name));
+        let body = serde_json::to_vec(config).unwrap();
+        let response: CreateResponse = self
+            .request(
+                "POST",
+                &path,
+                &[("Content-Type", "application/json")],
+                Some(&body),
+            )?
+            .ok()?
+            .json()?;
+        Ok(response.id)
+    }
+
+    /// Attaches to stdin of the container, which should be created with
+    /// `OpenStdin` and `StdinOnce`, so it's closed once the returned stream is
+    /// shut down.
+    pub fn attach_stdin(&self, container: &str) -> Result<ContainerStdin, DockerError> {
+        let path = format!("/containers/{}/attach?stream=1&stdin=1", encode(container));
+        let response = self.request(
+            "POST",
+            &path,
+            &[("Connection", "Upgrade"), ("Upgrade", "tcp")],
+            None,
+        )?;
+        // Daemon responds `101 Switching Protocols` and hijacks the
+        // connection, old ones respond `200 OK` instead.
+        if !matches!(response.status, 101 | 200) {
+            return Err(response.into_error());
+        }
+        Ok(ContainerStdin {
+            stream: response.body.into_inner().into_inner(),
+            path,
+        })
+    }
+
+    pub fn start_container(&self, container: &str) -> Result<(), DockerError> {
+        let path = format!("/containers/{}/start", encode(container));
+        self.request("POST", &path, &[], None)?.ok()?;
+        Ok(())
+    }
+
+    /// Waits for the container to stop and returns its exit status.
+    pub fn wait_container(&self, container: &str) -> Result<ContainerExit, DockerError> {
+        #[derive(Deserialize)]
+        struct WaitResponse {
+            #[serde(rename = "StatusCode")]
+            status_code: i64,
+            #[serde(rename = "Error")]
+            error: Option<WaitError>,
+        }
+        #[derive(Deserialize)]
+        struct WaitError {
+            #[serde(rename = "Message")]
+            message: String,
+        }
+
+        let path = format!("/containers/{}/wait", encode(container));
+        let response: WaitResponse = self.request("POST", &path, &[], None)?.ok()?.json()?;
+        let oom_killed = self.inspect_container(container)?["State"]["OOMKilled"]
+            .as_bool()
+            .unwrap_or(false);
+        Ok(ContainerExit {
+            status_code: response.status_code,
+            oom_killed,
+            error: response
+                .error
+                .map(|error| error.message)
+                .filter(|message| !message.is_empty()),
+        })
+    }
+
+    pub fn inspect_container(&self, container: &str) -> Result<Value, DockerError> {
+        let path = format!("/containers/{}/json", encode(container));
+        self.request("GET", &path, &[], None)?.ok()?.json()
+    }
+
+    /// Returns the host port that TCP `port` of the container is published on,
+    /// e.g. the ephemeral one assigned by the daemon for an empty `HostPort`.
+    pub fn host_port(&self, container: &str, port: u16) -> Result<u16, DockerError> {
+        let info = self.inspect_container(container)?;
+        info["NetworkSettings"]["Ports"][format!("{port}/tcp")]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find_map(|binding| binding["HostPort"].as_str()?.parse().ok())
+            .ok_or_else(|| {
+                invalid_response(
+                    "GET",
+                    &format!("/containers/{}/json", encode(container)),
+                    format!("port {port}/tcp is not published"),
+                )
+            })
+    }
+
+    /// Returns stdout and stderr of the container, following the new output
+    /// until the container stops if `follow` is set.
+    ///
+    /// The container should be created without TTY, so the output is
+    /// multiplexed.
+    pub fn logs(&self, container: &str, follow: bool) -> Result<ContainerLogs, DockerError> {
+        let path = format!(
+            "/containers/{}/logs?stdout=1&stderr=1&follow={}",
+            encode(container),
+            follow as u8
+        );
+        let response = self.request("GET", &path, &[], None)?.ok()?;
+        Ok(ContainerLogs {
+            body: response.body,
+            path,
+        })
+    }
+
+    /// Stops the container, it's killed if it doesn't stop in `timeout`.
+    /// Stopping a stopped container is a no-op.
+    pub fn stop_container(&self, container: &str, timeout: Duration) -> Result<(), DockerError> {
+        let path = format!(
+            "/containers/{}/stop?t={}",
+            encode(container),
+            timeout.as_secs()
+        );
+        let response = self.request("POST", &path, &[], None)?;
+        match response.status {
+            304 => Ok(()),
+            _ => response.ok().map(drop),
+        }
+    }
+
+    pub fn remove_container(&self, container: &str, force: bool) -> Result<(), DockerError> {
+        let path = format!("/containers/{}?force={}", encode(container), force as u8);
+        self.request("DELETE", &path, &[], None)?.ok()?;
+        Ok(())
+    }
+
+    fn request(
+        &self,
+        method: &'static str,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<Response, DockerError> {
+        let mut stream =
+            UnixStream::connect(&self.socket).map_err(|source| DockerError::Connect {
+                socket: self.socket.clone(),
+                source,
+            })?;
+
+        let mut head = format!("{method} {path} HTTP/1.1\r\nHost: docker\r\n");
+        for (key, value) in headers {
+            write!(head, "{key}: {value}\r\n").unwrap();
+        }
+        if !headers.iter().any(|(key, _)| *key == "Connection") {
+            head.push_str("Connection: close\r\n");
+        }
+        write!(
+            head,
+            "Content-Length: {}\r\n\r\n",
+            body.map_or(0, <[u8]>::len)
+        )
+        .unwrap();
+
+        let io_err = |source| io_err(method, path, source);
+        stream.write_all(head.as_bytes()).map_err(io_err)?;
+        if let Some(body) = body {
+            stream.write_all(body).map_err(io_err)?;
+        }
+        stream.flush().map_err(io_err)?;
+
+        Response::read(method, path, BufReader::new(stream))
+    }
+}
+
+/// Response of a Docker API request.
+struct Response {
+    method: &'static str,
+    path: String,
+    status: u16,
+    body: Body,
+}
+
+impl Response {
+    fn read(
+        method: &'static str,
+        path: &str,
+        mut reader: BufReader<UnixStream>,
+    ) -> Result<Self, DockerError> {
+        let invalid = |message: &str| invalid_response(method, path, message);
+
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|source| io_err(method, path, source))?;
+        let status = line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| invalid(&format!("invalid status line {line:?}")))?;
+
+        let mut content_length = None;
+        let mut chunked = false;
+        loop {
+            line.clear();
+            reader
+                .read_line(&mut line)
+                .map_err(|source| io_err(method, path, source))?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (key, value) = header
+                .split_once(':')
+                .ok_or_else(|| invalid(&format!("invalid header {header:?}")))?;
+            let value = value.trim();
+            if key.eq_ignore_ascii_case("content-length") {
+                content_length = Some(
+                    value
+                        .parse()
+                        .map_err(|_| invalid(&format!("invalid content length {value:?}")))?,
+                );
+            } else if key.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            }
+        }
+
+        let body = match (chunked, content_length) {
+            (true, _) => Body::Chunked {
+                reader,
+                remaining: 0,
+                done: false,
+            },
+            (false, Some(length)) => Body::Length(reader.take(length)),
+            (false, None) => Body::Eof(reader),
+        };
+
+        Ok(Self {
+            method,
+            path: path.to_string(),
+            status,
+            body,
+        })
+    }
+
+    /// Returns error with the message from daemon if the status is not 2xx.
+    fn ok(self) -> Result<Self, DockerError> {
+        if (200..300).contains(&self.status) {
+            Ok(self)
+        } else {
+            Err(self.into_error())
+        }
+    }
+
+    fn into_error(mut self) -> DockerError {
+        let mut body = String::new();
+        let _ = self.body.read_to_string(&mut body);
+        let message = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|value| value["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| body.trim().to_string());
+        DockerError::Api {
+            method: self.method,
+            path: self.path,
+            status: self.status,
+            message,
+        }
+    }
+
+    fn json<T: for<'de> Deserialize<'de>>(self) -> Result<T, DockerError> {
+        serde_json::from_reader(self.body)
+            .map_err(|err| invalid_response(self.method, &self.path, err))
+    }
+}
+
+/// Body of a response, delimited by its framing.
+enum Body {
+    Length(io::Take<BufReader<UnixStream>>),
+    Chunked {
+        reader: BufReader<UnixStream>,
+        /// Remaining bytes of the current chunk.
+        remaining: u64,
+        done: bool,
+    },
+    Eof(BufReader<UnixStream>),
+}
+
+impl Body {
+    fn into_inner(self) -> BufReader<UnixStream> {
+        match self {
+            Self::Length(reader) => reader.into_inner(),
+            Self::Chunked { reader, .. } | Self::Eof(reader) => reader,
+        }
+    }
+}
+
+impl Read for Body {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Length(reader) => reader.read(buf),
+            Self::Eof(reader) => reader.read(buf),
+            Self::Chunked {
+                reader,
+                remaining,
+                done,
+            } => {
+                if *done || buf.is_empty() {
+                    return Ok(0);
+                }
+                if *remaining == 0 {
+                    let mut line = String::new();
+                    reader.read_line(&mut line)?;
+                    let size = line.trim_end().split(';').next().unwrap_or_default();
+                    *remaining = u64::from_str_radix(size, 16).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("invalid chunk size {line:?}"),
+                        )
+                    })?;
+                    if *remaining == 0 {
+                        // Skip trailers until the empty line.
+                        loop {
+                            line.clear();
+                            if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                                break;
+                            }
+                        }
+                        *done = true;
+                        return Ok(0);
+                    }
+                }
+                let len = buf.len().min(*remaining as usize);
+                let read = reader.read(&mut buf[..len])?;
+                if read == 0 {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                *remaining -= read as u64;
+                if *remaining == 0 {
+                    // Consume the CRLF after chunk data.
+                    let mut crlf = [0; 2];
+                    reader.read_exact(&mut crlf)?;
+                }
+                Ok(read)
+            }
+        }
+    }
+}
+
+/// Stdin of a container returned by [`DockerEngine::attach_stdin`].
+pub struct ContainerStdin {
+    stream: UnixStream,
+    path: String,
+}
+
+impl ContainerStdin {
+    /// Writes all `data` then closes the stdin.
+    pub fn write_all_and_close(mut self, data: &[u8]) -> Result<(), DockerError> {
+        let io_err = |source| io_err("POST", &self.path, source);
+        self.stream.write_all(data).map_err(io_err)?;
+        self.stream.flush().map_err(io_err)?;
+        self.stream.shutdown(Shutdown::Write).map_err(io_err)?;
+        Ok(())
+    }
+}
+
+/// Multiplexed output of a container returned by [`DockerEngine::logs`],
+/// iterated in chunks.
+pub struct ContainerLogs {
+    body: Body,
+    path: String,
+}
+
+impl Iterator for ContainerLogs {
+    type Item = Result<(LogStream, Vec<u8>), DockerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Each frame has header `[stream, 0, 0, 0, size (u32 big endian)]`.
+        let mut header = [0; 8];
+        match self.body.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return None,
+            Err(source) => return Some(Err(io_err("GET", &self.path, source))),
+        }
+        let stream = match header[0] {
+            2 => LogStream::Stderr,
+            _ => LogStream::Stdout,
+        };
+        let size = u32::from_be_bytes(header[4..].try_into().unwrap()) as usize;
+        let mut data = vec![0; size];
+        if let Err(source) = self.body.read_exact(&mut data) {
+            return Some(Err(io_err("GET", &self.path, source)));
+        }
+        Some(Ok((stream, data)))
+    }
+}
+
+/// Returns the endpoint of the current docker context, `None` if the CLI is
+/// not available or fails.
+fn context_host() -> Option<String> {
+    let output = Command::new("docker")
+        .args([
+            "context",
+            "inspect",
+            "--format",
+            "{{.Endpoints.docker.Host}}",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let host = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !host.is_empty()).then_some(host)
+}
+
+/// Returns path of the `unix://` socket of the Docker host.
+fn socket_of(host: &str) -> Result<PathBuf, DockerError> {
+    host.strip_prefix("unix://")
+        .map(PathBuf::from)
+        .ok_or_else(|| DockerError::UnsupportedHost(host.to_string()))
+}
+
+fn io_err(method: &'static str, path: &str, source: io::Error) -> DockerError {
+    DockerError::Io {
+        method,
+        path: path.to_string(),
+        source,
+    }
+}
+
+fn invalid_response(method: &'static str, path: &str, message: impl ToString) -> DockerError {
+    DockerError::InvalidResponse {
+        method,
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Percent-encodes all bytes except unreserved characters.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => write!(encoded, "%{byte:02X}").unwrap(),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContainerExit, DockerEngine, DockerError, LogStream, socket_of};
+    use serde_json::json;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        os::unix::net::UnixListener,
+        thread::{self, JoinHandle},
+    };
+    use tempfile::TempDir;
+
+    /// Fake Docker daemon that replies each connection with the responses in
+    /// order, and returns the received request heads and bodies.
+    fn fake_daemon(responses: Vec<String>) -> (TempDir, DockerEngine, JoinHandle<Vec<String>>) {
+        let tempdir = TempDir::new().unwrap();
+        let socket = tempdir.path().join("docker.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body));
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+        (tempdir, DockerEngine::new(socket), handle)
+    }
+
+    fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn chunked_response(status: &str, chunks: &[&[u8]]) -> String {
+        let mut response = format!("HTTP/1.1 {status}\r\nTransfer-Encoding: chunked\r\n\r\n");
+        for chunk in chunks {
+            response.push_str(&format!("{:x}\r\n", chunk.len()));
+            response.push_str(std::str::from_utf8(chunk).unwrap());
+            response.push_str("\r\n");
+        }
+        response.push_str("0\r\n\r\n");
+        response
+    }
+
+    #[test]
+    fn test_image_exists() {
+        let (_tempdir, engine, handle) = fake_daemon(vec![
+            response("200 OK", "{}"),
+            response("404 Not Found", r#"{"message":"No such image"}"#),
+        ]);
+        assert!(engine.image_exists("ere-base:0.0.14").unwrap());
+        assert!(!engine.image_exists("ere-base:latest").unwrap());
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /images/ere-base%3A0.0.14/json HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn test_api_error() {
+        let (_tempdir, engine, handle) = fake_daemon(vec![response(
+            "409 Conflict",
+            r#"{"message":"container name is already in use"}"#,
+        )]);
+        let err = engine
+            .create_container("ere-server", &json!({ "Image": "ere-server" }))
+            .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                DockerError::Api { status: 409, message, .. }
+                    if message == "container name is already in use"
+            ),
+            "Unexpected error: {err}"
+        );
+        let requests = handle.join().unwrap();
+        assert!(requests[0].ends_with(r#"{"Image":"ere-server"}"#));
+    }
+
+    #[test]
+    fn test_wait_container() {
+        let (_tempdir, engine, handle) = fake_daemon(vec![
+            response("200 OK", r#"{"StatusCode":137,"Error":null}"#),
+            response("200 OK", r#"{"State":{"OOMKilled":true}}"#),
+        ]);
+        let exit = engine.wait_container("ere-compiler").unwrap();
+        assert_eq!(
+            exit,
+            ContainerExit {
+                status_code: 137,
+                oom_killed: true,
+                error: None
+            }
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_host_port() {
+        let ports = r#"{"3000/tcp":[{"HostIp":"0.0.0.0","HostPort":"32768"},{"HostIp":"::","HostPort":"32768"}]}"#;
+        let (_tempdir, engine, handle) = fake_daemon(vec![
+            response(
+                "200 OK",
+                &format!(r#"{{"NetworkSettings":{{"Ports":{ports}}}}}"#),
+            ),
+            response("200 OK", r#"{"NetworkSettings":{"Ports":{}}}"#),
+        ]);
+        assert_eq!(engine.host_port("ere-server", 3000).unwrap(), 32768);
+        assert!(matches!(
+            engine.host_port("ere-server", 3000).unwrap_err(),
+            DockerError::InvalidResponse { .. }
+        ));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_logs() {
+        let mut frames = Vec::new();
+        for (stream, data) in [(1, "compiling\n"), (2, "warning\n")] {
+            frames.extend([stream, 0, 0, 0]);
+            frames.extend((data.len() as u32).to_be_bytes());
+            frames.extend(data.as_bytes());
+        }
+        // Split in the middle of a frame to test chunked framing.
+        let (first, second) = frames.split_at(5);
+        let (_tempdir, engine, handle) =
+            fake_daemon(vec![chunked_response("200 OK", &[first, second])]);
+        let logs = engine
+            .logs("ere-compiler", true)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            logs,
+            [
+                (LogStream::Stdout, b"compiling\n".to_vec()),
+                (LogStream::Stderr, b"warning\n".to_vec()),
+            ]
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_connect_error() {
+        let tempdir = TempDir::new().unwrap();
+        let engine = DockerEngine::new(tempdir.path().join("docker.sock"));
+        assert!(matches!(
+            engine.image_exists("ere-base").unwrap_err(),
+            DockerError::Connect { .. }
+        ));
+    }
+
+    #[test]
+    fn test_socket_of() {
+        assert_eq!(
+            socket_of("unix:///run/user/1000/docker.sock").unwrap(),
+            std::path::Path::new("/run/user/1000/docker.sock")
+        );
+        for host in [
+            "tcp://127.0.0.1:2375",
+            "ssh://user@host",
+            "npipe:////./pipe/docker",
+        ] {
+            assert!(matches!(
+                socket_of(host).unwrap_err(),
+                DockerError::UnsupportedHost(unsupported) if unsupported == host
+            ));
+        }
+    }
+}
@@ -1,12 +1,26 @@
+use crate::{ErezkVM, docker::DockerError};
 use ere_server::client::{self, TwirpErrorResponse};
-use std::{io, path::PathBuf};
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
+use std::{io, path::PathBuf, process::ExitStatus, time::Duration};
 use thiserror::Error;
+use tokio::task::JoinError;
 
 impl From<client::Error> for Error {
     fn from(value: client::Error) -> Self {
         match value {
             client::Error::zkVM(err) => Self::zkVM(err),
-            client::Error::ConnectionTimeout => Self::ConnectionTimeout,
+            client::Error::ConnectionTimeout { attempts, elapsed } => {
+                Self::ConnectionTimeout { attempts, elapsed }
+            }
+            client::Error::VersionMismatch {
+                client,
+                server,
+                server_version,
+            } => Self::ServerVersionMismatch {
+                client,
+                server,
+                server_version,
+            },
             client::Error::Rpc(err) => Self::Rpc(err),
         }
     }
@@ -15,6 +29,9 @@ impl From<client::Error> for Error {
 #[derive(Debug, Error)]
 #[allow(non_camel_case_types)]
 pub enum Error {
+    #[error(transparent)]
+    CommonError(#[from] CommonError),
+
     #[error(
         "Guest directory must be in mounting directory, mounting_directory: {mounting_directory}, guest_directory: {guest_directory}"
     )]
@@ -28,20 +45,46 @@ pub enum Error {
         source: io::Error,
         context: String,
     },
-    #[error("Failed to execute `docker image`: {0}")]
-    DockerImageCmd(io::Error),
-    #[error("Failed to execute `docker build`: {0}")]
-    DockerBuildCmd(io::Error),
-    #[error("Failed to execute `docker run`: {0}")]
-    DockerRunCmd(io::Error),
-    #[error("Failed to execute `docker container`: {0}")]
-    DockerContainerCmd(io::Error),
+    #[error(transparent)]
+    Docker(#[from] DockerError),
     #[error("zkVM method error: {0}")]
-    zkVM(String),
-    #[error("Connection to zkVM server timeout after 5 minutes")]
-    ConnectionTimeout,
+    zkVM(zkVMError),
+    #[error("zkVM server is not healthy after {attempts} attempts in {elapsed:?}")]
+    ConnectionTimeout { attempts: u32, elapsed: Duration },
+    #[error(
+        "zkVM server (version {server_version}) speaks protocol version {server}, the host speaks {client}"
+    )]
+    ServerVersionMismatch {
+        client: u32,
+        server: u32,
+        server_version: String,
+    },
+    #[error("Invalid capabilities reported by zkVM server: {0}")]
+    InvalidServerCapabilities(#[source] serde_json::Error),
+    #[error("Unsupported zkVM {0} of the zkVM server")]
+    UnsupportedServerzkVM(String),
+    #[error("zkVM server runs {actual}, expect {expected}")]
+    ServerzkVMMismatch { expected: ErezkVM, actual: ErezkVM },
+    #[error("zkVM server didn't report its port in {0:?}")]
+    ServerPortTimeout(Duration),
+    #[error("zkVM server exited before being healthy: {0}")]
+    ServerExited(ExitStatus),
+    #[error(
+        "zkVM server container of {zkvm} exited unexpectedly, last logs:\n{}",
+        logs.join("\n")
+    )]
+    ServerContainerExited {
+        zkvm: ErezkVM,
+        /// The last lines of the server logs.
+        logs: Vec<String>,
+        /// Error returned by the request that failed because of the exit.
+        #[source]
+        source: Option<zkVMError>,
+    },
     #[error("RPC to zkVM server error: {0}")]
     Rpc(TwirpErrorResponse),
+    #[error("Blocking task failed: {0}")]
+    SpawnBlocking(JoinError),
 }
 
 impl Error {
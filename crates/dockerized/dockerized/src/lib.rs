@@ -22,6 +22,12 @@
 //! To force rebuild all images, set the environment variable
 //! `ERE_FORCE_REBUILD_DOCKER_IMAGE` to non-empty value.
 //!
+//! Images are built by `docker build` with BuildKit, and containers are run
+//! through the Docker Engine API over the Unix socket of the same daemon, which
+//! is the one in `DOCKER_HOST` (e.g. `unix:///run/user/1000/docker.sock`) if
+//! set, otherwise the one of the current docker context. Daemons not listening
+//! on a Unix socket (e.g. `tcp://` or `ssh://`) are not supported.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -67,23 +73,35 @@
 
 use crate::{
     cuda::cuda_arch,
-    docker::{DockerBuildCmd, DockerRunCmd, docker_image_exists, stop_docker_container},
+    docker::{
+        DockerBuildCmd, DockerRunCmd, docker_host_port, docker_image_exists, stop_docker_container,
+    },
+    logs::ServerLogs,
 };
-use ere_server::client::{Url, zkVMClient};
+use ere_server::client::zkVMClient;
 use ere_zkvm_interface::{
     compiler::Compiler,
     zkvm::{
-        ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind, ProverResourceType,
-        PublicValues, zkVM,
+        AsynczkVM, ExecuteOptions, KEY_CACHE_DIR_ENV, KeyCache, ProgramExecutionReport,
+        ProgramProvingReport, Proof, ProofKind, ProveOptions, ProverResourceType, PublicValues,
+        zkVM, zkVMError, zkVMProgramDigest,
     },
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     env,
     fmt::{self, Display, Formatter},
-    fs, iter,
+    fs,
     path::{Path, PathBuf},
+    process,
     str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
 };
 use tempfile::TempDir;
 use tracing::error;
@@ -91,16 +109,43 @@ use tracing::error;
 mod cuda;
 mod docker;
 mod error;
-
+mod logs;
+mod native;
+mod options;
+mod pool;
+mod remote;
+
+pub use docker::DockerError;
+pub use ere_server::client::{ConnectOptions, ServerCapabilities, ServerInfo, Url};
 pub use error::Error;
+pub use logs::SERVER_LOG_TAIL_LINES;
+pub use native::EreNativezkVM;
+pub use options::DockerizedOptions;
+pub use pool::{DEFAULT_MAX_IDLE_PER_ZKVM, EreDockerizedPool, PooledzkVM};
+pub use remote::EreRemotezkVM;
 
 include!(concat!(env!("OUT_DIR"), "/crate_version.rs"));
 include!(concat!(env!("OUT_DIR"), "/zkvm_sdk_version_impl.rs"));
 
-/// Offset of port used for `ere-server` for [`ErezkVM`]s.
-const ERE_SERVER_PORT_OFFSET: u16 = 4174;
+/// Time to wait for the server container to exit after a request fails, to
+/// tell whether the failure is caused by the exit.
+const SERVER_EXIT_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Time to wait for the server to list its jobs, see
+/// [`EreDockerizedzkVM::has_pending_jobs`].
+const PENDING_JOBS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Port the server listens on in the container, published on an ephemeral
+/// host port.
+const SERVER_PORT: u16 = 3000;
+
+/// Directory in the container to write the port file, mounted from the host.
+const SERVER_PORT_DIR: &str = "/var/run/ere-server";
+
+/// Name of the file the server writes its bound port to.
+const PORT_FILE: &str = "port";
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ErezkVM {
     Airbender,
     Jolt,
@@ -187,7 +232,7 @@ impl ErezkVM {
                 cmd = cmd.build_arg("CUDA", "1");
             }
 
-            cmd.exec(&workspace_dir).map_err(Error::DockerBuildCmd)?;
+            cmd.exec(&workspace_dir)?;
         }
 
         // Build `ere-base-{zkvm}`
@@ -212,7 +257,7 @@ impl ErezkVM {
                 }
             }
 
-            cmd.exec(&workspace_dir).map_err(Error::DockerBuildCmd)?;
+            cmd.exec(&workspace_dir)?;
         }
 
         // Build `ere-compiler-{zkvm}`
@@ -222,8 +267,7 @@ impl ErezkVM {
                 .tag(self.compiler_zkvm_image(CRATE_VERSION))
                 .tag(self.compiler_zkvm_image("latest"))
                 .build_arg("BASE_ZKVM_IMAGE", self.base_zkvm_image(CRATE_VERSION, gpu))
-                .exec(&workspace_dir)
-                .map_err(Error::DockerBuildCmd)?;
+                .exec(&workspace_dir)?;
         }
 
         // Build `ere-server-{zkvm}`
@@ -238,30 +282,78 @@ impl ErezkVM {
                 cmd = cmd.build_arg("CUDA", "1");
             }
 
-            cmd.exec(&workspace_dir).map_err(Error::DockerBuildCmd)?;
+            cmd.exec(&workspace_dir)?;
         }
 
         Ok(())
     }
 
-    fn server_port(&self) -> u16 {
-        ERE_SERVER_PORT_OFFSET + *self as u16
+    /// Returns the capabilities reported by the `ere-server` binary in the
+    /// image, i.e. the [`zkVMCapabilities`] of the zkVM crate it's built with.
+    ///
+    /// The image should be built by [`ErezkVM::build_docker_image`] first.
+    /// The result is cached per image in the current process.
+    ///
+    /// [`zkVMCapabilities`]: ere_zkvm_interface::zkvm::zkVMCapabilities
+    pub fn server_capabilities(&self, gpu: bool) -> Result<ServerCapabilities, Error> {
+        static CAPABILITIES: Mutex<BTreeMap<String, ServerCapabilities>> =
+            Mutex::new(BTreeMap::new());
+
+        let image = self.server_zkvm_image(CRATE_VERSION, gpu);
+        if let Some(capabilities) = CAPABILITIES.lock().unwrap().get(&image) {
+            return Ok(capabilities.clone());
+        }
+
+        let output = DockerRunCmd::new(image.clone())
+            .rm()
+            .output(["--capabilities"])?;
+        let capabilities: ServerCapabilities =
+            serde_json::from_slice(&output).map_err(Error::InvalidServerCapabilities)?;
+
+        CAPABILITIES
+            .lock()
+            .unwrap()
+            .insert(image, capabilities.clone());
+        Ok(capabilities)
     }
 
     fn spawn_server(
         &self,
         program: &SerializedProgram,
         resource: &ProverResourceType,
+        options: &DockerizedOptions,
     ) -> Result<ServerContainer, Error> {
-        let port = self.server_port().to_string();
-        let name = format!("ere-server-{self}-{port}");
+        // Unique container name per instance, so several instances of the
+        // same zkVM can run concurrently.
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let name = format!(
+            "ere-server-{self}-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
         let gpu = matches!(resource, ProverResourceType::Gpu);
+        // SP1 runs docker command to spin up the server to do GPU proving, to
+        // give the client access to the prover service, we need to use the
+        // host networking driver, where ports can't be published. So the
+        // server binds an ephemeral port on the host and reports it by port
+        // file instead.
+        let host_network = gpu && matches!(self, Self::SP1);
         let mut cmd = DockerRunCmd::new(self.server_zkvm_image(CRATE_VERSION, gpu))
             .rm()
             .inherit_env("RUST_LOG")
             .inherit_env("NO_COLOR")
-            .publish(&port, &port)
             .name(&name);
+        if !host_network {
+            // Published on an ephemeral host port, looked up after started.
+            cmd = cmd.publish("", SERVER_PORT.to_string());
+        }
+
+        // Share the key cache with the server if it's enabled.
+        if let Some(dir) = KeyCache::from_env().dir() {
+            cmd = cmd
+                .volume(dir, dir)
+                .env(KEY_CACHE_DIR_ENV, dir.to_string_lossy());
+        }
 
         // zkVM specific options
         cmd = match self {
@@ -271,8 +363,8 @@ impl ErezkVM {
             // ZisK uses shared memory to exchange data between processes, it
             // requires at least 8G shared memory, here we set 16G for safety.
             Self::Zisk => cmd
-                .option("shm-size", "16G")
-                .option("ulimit", "memlock=-1:-1")
+                .shm_size("16G")
+                .ulimit("memlock", -1, -1)
                 .inherit_env("ZISK_PORT")
                 .inherit_env("ZISK_CHUNK_SIZE_BITS")
                 .inherit_env("ZISK_UNLOCK_MAPPED_MEMORY")
@@ -290,9 +382,6 @@ impl ErezkVM {
             cmd = match self {
                 Self::Airbender => cmd.gpus("all"),
                 Self::OpenVM => cmd.gpus("all"),
-                // SP1 runs docker command to spin up the server to do GPU
-                // proving, to give the client access to the prover service, we
-                // need to use the host networking driver.
                 Self::SP1 => cmd.mount_docker_socket().network("host"),
                 Self::Risc0 => cmd.gpus("all").inherit_env("RISC0_DEFAULT_PROVER_NUM_GPUS"),
                 Self::Zisk => cmd.gpus("all"),
@@ -328,12 +417,38 @@ impl ErezkVM {
             _ => cmd,
         };
 
-        let args = iter::empty()
-            .chain(["--port", &port])
-            .chain(resource.to_args());
-        cmd.spawn(args, &program.0).map_err(Error::DockerRunCmd)?;
+        let server_port = SERVER_PORT.to_string();
+        let port_file = format!("{SERVER_PORT_DIR}/{PORT_FILE}");
+        let port_args = if host_network {
+            let port_dir = tempdir.path().join("server");
+            fs::create_dir(&port_dir)
+                .map_err(|err| Error::io(err, "Failed to create port file directory"))?;
+            cmd = cmd.volume(&port_dir, SERVER_PORT_DIR);
+            vec!["--port", "0", "--port-file", &port_file]
+        } else {
+            vec!["--port", &server_port]
+        };
+
+        cmd = options.apply(cmd);
+
+        // Created before spawning, so the container is removed even if
+        // spawning fails halfway.
+        let mut container = ServerContainer {
+            name,
+            port: None,
+            logs: ServerLogs::default(),
+            tempdir,
+        };
+
+        let args = port_args.into_iter().chain(resource.to_args());
+        let logs = cmd.spawn(args, &program.0)?;
+        container.logs.forward(*self, &container.name, logs);
 
-        Ok(ServerContainer { name, tempdir })
+        if !host_network {
+            container.port = Some(docker_host_port(&container.name, SERVER_PORT)?);
+        }
+
+        Ok(container)
     }
 }
 
@@ -383,7 +498,7 @@ impl EreDockerizedCompiler {
 }
 
 /// Wrapper for serialized program.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SerializedProgram(Vec<u8>);
 
 impl Compiler for EreDockerizedCompiler {
@@ -421,8 +536,7 @@ impl Compiler for EreDockerizedCompiler {
             guest_path_in_docker.to_string_lossy().as_ref(),
             "--output-path",
             "/output/program",
-        ])
-        .map_err(Error::DockerRunCmd)?;
+        ])?;
 
         let program_path = tempdir.path().join("program");
         let program = fs::read(&program_path).map_err(|err| {
@@ -440,10 +554,28 @@ impl Compiler for EreDockerizedCompiler {
 
 struct ServerContainer {
     name: String,
-    #[allow(dead_code)]
+    /// Host port of the server, `None` if the server is on the host network
+    /// and reports its port by [`ServerContainer::port_file`].
+    port: Option<u16>,
+    logs: ServerLogs,
     tempdir: TempDir,
 }
 
+impl ServerContainer {
+    /// Returns the host path of the port file written by the server.
+    fn port_file(&self) -> PathBuf {
+        self.tempdir.path().join("server").join(PORT_FILE)
+    }
+
+    /// Connects to the server once it's healthy.
+    async fn connect(&self, options: &ConnectOptions) -> Result<EreRemotezkVM, Error> {
+        match self.port {
+            Some(port) => EreRemotezkVM::connect_async(local_url(port), options).await,
+            None => connect_port_file(&self.port_file(), options).await,
+        }
+    }
+}
+
 impl Drop for ServerContainer {
     fn drop(&mut self) {
         if let Err(err) = stop_docker_container(&self.name) {
@@ -456,30 +588,89 @@ pub struct EreDockerizedzkVM {
     zkvm: ErezkVM,
     program: SerializedProgram,
     resource: ProverResourceType,
+    options: DockerizedOptions,
     #[allow(dead_code)]
     server_container: ServerContainer,
-    client: zkVMClient,
+    remote: EreRemotezkVM,
 }
 
 impl EreDockerizedzkVM {
+    /// Same as [`EreDockerizedzkVM::new_with_options`] with the default
+    /// [`DockerizedOptions`].
     pub fn new(
         zkvm: ErezkVM,
         program: SerializedProgram,
         resource: ProverResourceType,
     ) -> Result<Self, Error> {
-        zkvm.build_docker_image(matches!(resource, ProverResourceType::Gpu))?;
+        Self::new_with_options(zkvm, program, resource, DockerizedOptions::default())
+    }
+
+    /// Builds the images if needed, then spawns the server container with
+    /// the resource limits and placement configured by `options`.
+    pub fn new_with_options(
+        zkvm: ErezkVM,
+        program: SerializedProgram,
+        resource: ProverResourceType,
+        options: DockerizedOptions,
+    ) -> Result<Self, Error> {
+        block_on(Self::new_async_with_options(
+            zkvm, program, resource, options,
+        ))
+    }
 
-        let server_container = zkvm.spawn_server(&program, &resource)?;
+    /// Same as [`EreDockerizedzkVM::new`] but doesn't block the async runtime.
+    pub async fn new_async(
+        zkvm: ErezkVM,
+        program: SerializedProgram,
+        resource: ProverResourceType,
+    ) -> Result<Self, Error> {
+        Self::new_async_with_options(zkvm, program, resource, DockerizedOptions::default()).await
+    }
 
-        let url = Url::parse(&format!("http://127.0.0.1:{}", zkvm.server_port())).unwrap();
-        let client = block_on(zkVMClient::new(url))?;
+    /// Same as [`EreDockerizedzkVM::new_with_options`] but doesn't block the
+    /// async runtime, the image building and container spawning are done in
+    /// [`tokio::task::spawn_blocking`].
+    pub async fn new_async_with_options(
+        zkvm: ErezkVM,
+        program: SerializedProgram,
+        resource: ProverResourceType,
+        options: DockerizedOptions,
+    ) -> Result<Self, Error> {
+        let (program, resource, options, server_container) =
+            tokio::task::spawn_blocking(move || {
+                let gpu = matches!(resource, ProverResourceType::Gpu);
+                zkvm.build_docker_image(gpu)?;
+                zkvm.server_capabilities(gpu)?
+                    .check_prover_resource(resource.kind())?;
+
+                let server_container = zkvm.spawn_server(&program, &resource, &options)?;
+
+                Ok::<_, Error>((program, resource, options, server_container))
+            })
+            .await
+            .map_err(Error::SpawnBlocking)??;
+
+        // Fail fast with the server logs if the container exits before being
+        // healthy.
+        let connect_options = ConnectOptions::default();
+        let remote = tokio::select! {
+            remote = server_container.connect(&connect_options) => remote?,
+            _ = server_container.logs.exited() => {
+                return Err(Error::ServerContainerExited {
+                    zkvm,
+                    logs: server_container.logs.tail(),
+                    source: None,
+                });
+            }
+        };
 
         Ok(Self {
             zkvm,
             program,
             resource,
+            options,
             server_container,
-            client,
+            remote,
         })
     }
 
@@ -494,31 +685,122 @@ impl EreDockerizedzkVM {
     pub fn resource(&self) -> &ProverResourceType {
         &self.resource
     }
+
+    pub fn options(&self) -> &DockerizedOptions {
+        &self.options
+    }
+
+    /// Returns the last lines of the server logs.
+    pub fn server_logs(&self) -> Vec<String> {
+        self.server_container.logs.tail()
+    }
+
+    /// Replaces the program of the server container without restarting it,
+    /// which skips the container start of creating a new instance, but the
+    /// zkVM is still set up from scratch for the new program.
+    ///
+    /// Fails if any request or job is pending on the server, see
+    /// [`EreRemotezkVM::load_program`].
+    pub fn load_program(&mut self, program: SerializedProgram) -> Result<(), Error> {
+        block_on(self.load_program_async(program))
+    }
+
+    /// Same as [`EreDockerizedzkVM::load_program`] but doesn't block the async runtime.
+    pub async fn load_program_async(&mut self, program: SerializedProgram) -> Result<(), Error> {
+        self.remote.load_program_async(&program).await?;
+        self.program = program;
+        Ok(())
+    }
+
+    /// Returns whether the server container has exited.
+    pub(crate) fn is_server_exited(&self) -> bool {
+        self.server_container.logs.is_exited()
+    }
+
+    /// Returns whether the server has queued or running jobs, or fails to
+    /// list them.
+    ///
+    /// It's checked in another thread with its own runtime and connection, so
+    /// it can be called on drop in any context, including a `current_thread`
+    /// runtime.
+    pub(crate) fn has_pending_jobs(&self) -> bool {
+        let url = self.remote.url().clone();
+        thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .ok()?;
+                    runtime.block_on(async {
+                        let options = ConnectOptions::default()
+                            .with_timeout(PENDING_JOBS_TIMEOUT)
+                            .with_max_retries(0);
+                        let client = zkVMClient::connect(url, &options).await.ok()?;
+                        let jobs = client.list_jobs().await.ok()?;
+                        Some(jobs.iter().any(|job| !job.state.is_finished()))
+                    })
+                })
+                .join()
+                .ok()
+                .flatten()
+                .unwrap_or(true)
+        })
+    }
+
+    /// Same as [`EreDockerizedzkVM::has_pending_jobs`] but checks with the
+    /// existing connection without blocking the async runtime.
+    pub(crate) async fn has_pending_jobs_async(&self) -> bool {
+        match self.remote.client().list_jobs().await {
+            Ok(jobs) => jobs.iter().any(|job| !job.state.is_finished()),
+            Err(_) => true,
+        }
+    }
+
+    /// Attaches the server logs to `err` if the server container has exited,
+    /// which is likely the cause of the error (e.g. killed by OOM).
+    async fn attach_server_logs(&self, err: zkVMError) -> zkVMError {
+        if !matches!(err, zkVMError::Infrastructure(_)) {
+            return err;
+        }
+        // The request might fail slightly before the log stream ends.
+        let logs = &self.server_container.logs;
+        if tokio::time::timeout(SERVER_EXIT_GRACE_PERIOD, logs.exited())
+            .await
+            .is_err()
+        {
+            return err;
+        }
+        zkVMError::infrastructure(Error::ServerContainerExited {
+            zkvm: self.zkvm,
+            logs: logs.tail(),
+            source: Some(err),
+        })
+    }
 }
 
 impl zkVM for EreDockerizedzkVM {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
-        let (public_values, report) =
-            block_on(self.client.execute(input.to_vec())).map_err(Error::from)?;
-
-        Ok((public_values, report))
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        zkVM::execute_with_options(&self.remote, input, options)
+            .map_err(|err| block_on(self.attach_server_logs(err)))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        let (public_values, proof, report) =
-            block_on(self.client.prove(input.to_vec(), proof_kind)).map_err(Error::from)?;
-
-        Ok((public_values, proof, report))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        zkVM::prove_with_options(&self.remote, input, proof_kind, options)
+            .map_err(|err| block_on(self.attach_server_logs(err)))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let public_values = block_on(self.client.verify(proof)).map_err(Error::from)?;
-
-        Ok(public_values)
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        zkVM::verify(&self.remote, proof).map_err(|err| block_on(self.attach_server_logs(err)))
     }
 
     fn name(&self) -> &'static str {
@@ -530,6 +812,56 @@ impl zkVM for EreDockerizedzkVM {
     }
 }
 
+impl AsynczkVM for EreDockerizedzkVM {
+    async fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        match AsynczkVM::execute_with_options(&self.remote, input, options).await {
+            Ok(output) => Ok(output),
+            Err(err) => Err(self.attach_server_logs(err).await),
+        }
+    }
+
+    async fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        match AsynczkVM::prove_with_options(&self.remote, input, proof_kind, options).await {
+            Ok(output) => Ok(output),
+            Err(err) => Err(self.attach_server_logs(err).await),
+        }
+    }
+
+    async fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        match AsynczkVM::verify(&self.remote, proof).await {
+            Ok(output) => Ok(output),
+            Err(err) => Err(self.attach_server_logs(err).await),
+        }
+    }
+}
+
+impl zkVMProgramDigest for EreDockerizedzkVM {
+    /// Program digest of the zkVM in server, serialized by
+    /// [`zkVMProgramDigest::program_digest_bytes`].
+    type ProgramDigest = Vec<u8>;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        self.remote.program_digest()
+    }
+
+    fn program_digest_bytes(&self) -> Result<Vec<u8>, zkVMError> {
+        self.remote.program_digest_bytes()
+    }
+}
+
+/// Blocks on the `future`, in the current runtime if any, otherwise in a new
+/// runtime.
+///
+/// It panics in a `current_thread` runtime, use [`AsynczkVM`] methods there.
 fn block_on<T>(future: impl Future<Output = T>) -> T {
     match tokio::runtime::Handle::try_current() {
         Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
@@ -537,6 +869,37 @@ fn block_on<T>(future: impl Future<Output = T>) -> T {
     }
 }
 
+/// Returns URL of the server listening on `port` of the host.
+fn local_url(port: u16) -> Url {
+    Url::parse(&format!("http://127.0.0.1:{port}")).unwrap()
+}
+
+/// Waits for the server to write its bound port to `port_file` (see
+/// `--port-file` of `ere-server`), then connects to it once it's healthy,
+/// both within `options.timeout`.
+async fn connect_port_file(
+    port_file: &Path,
+    options: &ConnectOptions,
+) -> Result<EreRemotezkVM, Error> {
+    let start = Instant::now();
+    let port = loop {
+        let port = fs::read_to_string(port_file)
+            .ok()
+            .and_then(|port| port.trim().parse().ok());
+        if let Some(port) = port {
+            break port;
+        }
+        if start.elapsed() + options.retry_interval > options.timeout {
+            return Err(Error::ServerPortTimeout(start.elapsed()));
+        }
+        tokio::time::sleep(options.retry_interval).await;
+    };
+    let options = options
+        .clone()
+        .with_timeout(options.timeout.saturating_sub(start.elapsed()));
+    EreRemotezkVM::connect_async(local_url(port), &options).await
+}
+
 fn workspace_dir() -> PathBuf {
     let mut dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     dir.pop();
@@ -552,14 +915,16 @@ fn home_dir() -> PathBuf {
 #[cfg(test)]
 mod test {
     use crate::{
-        EreDockerizedCompiler, EreDockerizedzkVM, ErezkVM, Error, SerializedProgram, workspace_dir,
+        ConnectOptions, EreDockerizedCompiler, EreDockerizedzkVM, ErezkVM, Error,
+        SerializedProgram, block_on, connect_port_file, workspace_dir,
     };
     use ere_test_utils::{host::*, program::basic::BasicProgramInput};
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{CommonError, ProofKind, ProverResourceType, zkVM, zkVMError, zkVMProgramDigest},
     };
-    use std::sync::{Mutex, MutexGuard, OnceLock};
+    use std::{sync::OnceLock, time::Duration};
+    use tempfile::TempDir;
 
     macro_rules! test_compile {
         ($zkvm:ident, $program:literal) => {
@@ -578,13 +943,9 @@ mod test {
             }
 
             #[allow(dead_code)]
-            fn zkvm() -> (MutexGuard<'static, ()>, EreDockerizedzkVM) {
-                static LOCK: Mutex<()> = Mutex::new(());
-                let guard = LOCK.lock().unwrap();
+            fn zkvm() -> EreDockerizedzkVM {
                 let zkvm = ErezkVM::$zkvm;
-                let zkvm = EreDockerizedzkVM::new(zkvm, program().clone(), ProverResourceType::Cpu)
-                    .unwrap();
-                (guard, zkvm)
+                EreDockerizedzkVM::new(zkvm, program().clone(), ProverResourceType::Cpu).unwrap()
             }
 
             #[test]
@@ -600,7 +961,7 @@ mod test {
         ($zkvm:ident, $valid_test_case:expr, $invalid_test_cases:expr) => {
             #[test]
             fn test_execute() {
-                let (_guard, zkvm) = zkvm();
+                let zkvm = zkvm();
 
                 // Valid test case
                 run_zkvm_execute(&zkvm, &$valid_test_case);
@@ -608,9 +969,18 @@ mod test {
                 // Invalid test cases
                 for input in $invalid_test_cases {
                     let err = zkvm.execute(&input).unwrap_err();
-                    assert!(matches!(err.downcast::<Error>().unwrap(), Error::zkVM(_)),);
+                    assert!(
+                        matches!(
+                            err,
+                            zkVMError::GuestPanic { .. } | zkVMError::InvalidInput(_)
+                        ),
+                        "Unexpected error: {err}"
+                    );
                 }
 
+                // Program digest
+                assert!(!zkvm.program_digest_bytes().unwrap().is_empty());
+
                 drop(zkvm);
             }
         };
@@ -620,15 +990,14 @@ mod test {
         ($zkvm:ident, $valid_test_case:expr, $invalid_test_cases:expr) => {
             #[test]
             fn test_prove() {
-                let (_guard, zkvm) = zkvm();
+                let zkvm = zkvm();
 
                 // Valid test case
                 run_zkvm_prove(&zkvm, &$valid_test_case);
 
                 // Invalid test cases
                 for input in $invalid_test_cases {
-                    let err = zkvm.prove(&input, ProofKind::default()).unwrap_err();
-                    assert!(matches!(err.downcast::<Error>().unwrap(), Error::zkVM(_)),);
+                    zkvm.prove(&input, ProofKind::default()).unwrap_err();
                 }
 
                 drop(zkvm);
@@ -636,6 +1005,29 @@ mod test {
         };
     }
 
+    #[test]
+    fn test_unsupported_prover_resource_kind() {
+        let program = SerializedProgram(Vec::new());
+        let Err(err) = EreDockerizedzkVM::new(ErezkVM::Jolt, program, ProverResourceType::Gpu)
+        else {
+            panic!("GPU should be unsupported for Jolt");
+        };
+        assert!(matches!(
+            err,
+            Error::CommonError(CommonError::UnsupportedProverResourceKind { .. })
+        ));
+    }
+
+    #[test]
+    fn test_connect_port_file_timeout() {
+        let tempdir = TempDir::new().unwrap();
+        let options = ConnectOptions::default()
+            .with_timeout(Duration::from_millis(300))
+            .with_retry_interval(Duration::from_millis(100));
+        let result = block_on(connect_port_file(&tempdir.path().join("port"), &options));
+        assert!(matches!(result, Err(Error::ServerPortTimeout(_))));
+    }
+
     mod airbender {
         test_compile!(Airbender, "basic");
         test_execute!(
@@ -0,0 +1,99 @@
+use crate::{
+    ErezkVM,
+    docker::{ContainerLogs, LogStream},
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use tracing::{error, info, info_span, warn};
+
+/// Number of the last lines of server logs kept for errors.
+pub const SERVER_LOG_TAIL_LINES: usize = 100;
+
+/// Logs of the server container, forwarded into `tracing` line by line under
+/// span `ere_server` with the zkVM name, and the last
+/// [`SERVER_LOG_TAIL_LINES`] lines are kept to be attached to errors.
+#[derive(Clone, Default)]
+pub(crate) struct ServerLogs {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    tail: VecDeque<String>,
+    /// Set when the log stream ends, i.e. the container has stopped.
+    exited: bool,
+}
+
+impl ServerLogs {
+    /// Forwards `logs` in a background thread until the container stops.
+    pub(crate) fn forward(&self, zkvm: ErezkVM, container: &str, logs: ContainerLogs) {
+        let inner = self.inner.clone();
+        let span = info_span!("ere_server", zkvm = %zkvm, container = %container);
+        thread::spawn(move || {
+            let _guard = span.enter();
+            // Partial lines of stdout and stderr, which might be split into
+            // several chunks.
+            let mut partial = [Vec::new(), Vec::new()];
+            for chunk in logs {
+                let (stream, data) = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(err) => {
+                        error!("Failed to read server logs: {err}");
+                        break;
+                    }
+                };
+                let buf = &mut partial[stream as usize];
+                buf.extend(data);
+                while let Some(idx) = buf.iter().position(|byte| *byte == b'\n') {
+                    let line = buf.drain(..=idx).collect::<Vec<_>>();
+                    record(&inner, stream, &line);
+                }
+            }
+            for (stream, buf) in [LogStream::Stdout, LogStream::Stderr]
+                .into_iter()
+                .zip(partial)
+            {
+                if !buf.is_empty() {
+                    record(&inner, stream, &buf);
+                }
+            }
+            inner.lock().unwrap().exited = true;
+        });
+    }
+
+    /// Returns whether the container has stopped.
+    pub(crate) fn is_exited(&self) -> bool {
+        self.inner.lock().unwrap().exited
+    }
+
+    /// Waits for the container to stop.
+    pub(crate) async fn exited(&self) {
+        while !self.is_exited() {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Returns the last lines of logs.
+    pub(crate) fn tail(&self) -> Vec<String> {
+        self.inner.lock().unwrap().tail.iter().cloned().collect()
+    }
+}
+
+fn record(inner: &Mutex<Inner>, stream: LogStream, line: &[u8]) {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim_end();
+    match stream {
+        LogStream::Stdout => info!("{line}"),
+        LogStream::Stderr => warn!("{line}"),
+    }
+
+    let mut inner = inner.lock().unwrap();
+    if inner.tail.len() == SERVER_LOG_TAIL_LINES {
+        inner.tail.pop_front();
+    }
+    inner.tail.push_back(line.to_string());
+}
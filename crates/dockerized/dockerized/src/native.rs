@@ -0,0 +1,300 @@
+use crate::{
+    ConnectOptions, EreRemotezkVM, ErezkVM, PORT_FILE, SerializedProgram, ServerCapabilities,
+    block_on, connect_port_file, error::Error,
+};
+use ere_zkvm_interface::zkvm::{
+    AsynczkVM, CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProverResourceType, PublicValues, zkVM, zkVMError, zkVMProgramDigest,
+};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+use tempfile::TempDir;
+use tracing::error;
+
+/// Time to wait for the server to shutdown gracefully before killing it, same
+/// as the default of `docker stop`.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// zkVM that spawns the `ere-server` binary as a child process on the host,
+/// for machines that have the zkVM SDK installed but can't run Docker.
+///
+/// It behaves the same as [`EreDockerizedzkVM`], except that the server binary
+/// is built by the caller, e.g. by:
+///
+/// ```bash
+/// cargo build --release --package ere-server --bin ere-server --features sp1
+/// ```
+///
+/// The server inherits the environment variables of the current process, and
+/// it's shut down when the instance is dropped.
+///
+/// [`EreDockerizedzkVM`]: crate::EreDockerizedzkVM
+pub struct EreNativezkVM {
+    zkvm: ErezkVM,
+    program: SerializedProgram,
+    resource: ProverResourceType,
+    #[allow(dead_code)]
+    server_process: ServerProcess,
+    remote: EreRemotezkVM,
+}
+
+impl EreNativezkVM {
+    /// Spawns `server_binary`, which should be `ere-server` built with the
+    /// feature of `zkvm`, then connects to it once it's healthy.
+    pub fn new(
+        zkvm: ErezkVM,
+        server_binary: impl AsRef<Path>,
+        program: SerializedProgram,
+        resource: ProverResourceType,
+    ) -> Result<Self, Error> {
+        block_on(Self::new_async(zkvm, server_binary, program, resource))
+    }
+
+    /// Same as [`EreNativezkVM::new`] but doesn't block the async runtime.
+    pub async fn new_async(
+        zkvm: ErezkVM,
+        server_binary: impl AsRef<Path>,
+        program: SerializedProgram,
+        resource: ProverResourceType,
+    ) -> Result<Self, Error> {
+        let server_binary = server_binary.as_ref().to_path_buf();
+        let capabilities = {
+            let server_binary = server_binary.clone();
+            tokio::task::spawn_blocking(move || server_capabilities(&server_binary))
+                .await
+                .map_err(Error::SpawnBlocking)??
+        };
+        capabilities.check_prover_resource(resource.kind())?;
+
+        let mut server_process = ServerProcess::spawn(&server_binary, &program, &resource)?;
+
+        // Fail fast if the server exits before being healthy, e.g. the binary
+        // is built for another zkVM or the program is invalid.
+        let options = ConnectOptions::default();
+        let port_file = server_process.port_file();
+        let remote = tokio::select! {
+            remote = connect_port_file(&port_file, &options) => remote?,
+            status = server_process.exited() => return Err(Error::ServerExited(status?)),
+        };
+        if remote.zkvm() != zkvm {
+            return Err(Error::ServerzkVMMismatch {
+                expected: zkvm,
+                actual: remote.zkvm(),
+            });
+        }
+
+        Ok(Self {
+            zkvm,
+            program,
+            resource,
+            server_process,
+            remote,
+        })
+    }
+
+    pub fn zkvm(&self) -> ErezkVM {
+        self.zkvm
+    }
+
+    pub fn program(&self) -> &SerializedProgram {
+        &self.program
+    }
+
+    pub fn resource(&self) -> &ProverResourceType {
+        &self.resource
+    }
+
+    /// Replaces the program of the server without restarting it, which
+    /// skips spawning a new server, but the zkVM is still set up from scratch
+    /// for the new program.
+    ///
+    /// Fails if any request or job is pending on the server, see
+    /// [`EreRemotezkVM::load_program`].
+    pub fn load_program(&mut self, program: SerializedProgram) -> Result<(), Error> {
+        block_on(self.load_program_async(program))
+    }
+
+    /// Same as [`EreNativezkVM::load_program`] but doesn't block the async runtime.
+    pub async fn load_program_async(&mut self, program: SerializedProgram) -> Result<(), Error> {
+        self.remote.load_program_async(&program).await?;
+        self.program = program;
+        Ok(())
+    }
+}
+
+/// `ere-server` child process, which is shut down on drop.
+struct ServerProcess {
+    binary: PathBuf,
+    /// Directory of the port file written by the server.
+    tempdir: TempDir,
+    child: Child,
+}
+
+impl ServerProcess {
+    /// Spawns the server on an ephemeral port, which is reported by
+    /// [`ServerProcess::port_file`], and writes the serialized program to its
+    /// stdin.
+    fn spawn(
+        binary: &Path,
+        program: &SerializedProgram,
+        resource: &ProverResourceType,
+    ) -> Result<Self, Error> {
+        let tempdir =
+            TempDir::new().map_err(|err| Error::io(err, "Failed to create temporary directory"))?;
+
+        let child = Command::new(binary)
+            .args(["--port", "0", "--port-file"])
+            .arg(tempdir.path().join(PORT_FILE))
+            .args(resource.to_args())
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|err| Error::io(err, format!("Failed to spawn {}", binary.display())))?;
+        // Created before writing the program, so the server is shut down even
+        // if writing fails.
+        let mut server_process = Self {
+            binary: binary.to_path_buf(),
+            tempdir,
+            child,
+        };
+
+        // Write all to stdin then drop to close the pipe.
+        server_process
+            .child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(&program.0)
+            .map_err(|err| Error::io(err, "Failed to write program to server stdin"))?;
+
+        Ok(server_process)
+    }
+
+    /// Returns path of the file the server writes its bound port to.
+    fn port_file(&self) -> PathBuf {
+        self.tempdir.path().join(PORT_FILE)
+    }
+
+    /// Waits for the server to exit.
+    async fn exited(&mut self) -> Result<ExitStatus, Error> {
+        loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => return Ok(status),
+                Ok(None) => tokio::time::sleep(Duration::from_millis(100)).await,
+                Err(err) => return Err(Error::io(err, "Failed to wait for server process")),
+            }
+        }
+    }
+}
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        // Send `SIGTERM` for graceful shutdown, and kill the server if it
+        // doesn't exit in time.
+        let pid = self.child.id();
+        // SAFETY: `kill` only sends a signal, the pid is our own child which
+        // hasn't been waited on yet, so it can't have been reused.
+        if unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) } == 0 {
+            let start = Instant::now();
+            while start.elapsed() < SHUTDOWN_TIMEOUT {
+                if !matches!(self.child.try_wait(), Ok(None)) {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+
+        if let Err(err) = self.child.kill().and_then(|_| self.child.wait()) {
+            error!("Failed to kill {} ({pid}): {err}", self.binary.display());
+        }
+    }
+}
+
+/// Returns the capabilities printed by `ere-server --capabilities`.
+fn server_capabilities(binary: &Path) -> Result<ServerCapabilities, Error> {
+    let mut cmd = Command::new(binary);
+    cmd.arg("--capabilities");
+    let output = cmd
+        .output()
+        .map_err(|err| CommonError::command(&cmd, err))?;
+    if !output.status.success() {
+        Err(CommonError::command_exit_non_zero(
+            &cmd,
+            output.status,
+            Some(&output),
+        ))?
+    }
+    serde_json::from_slice(&output.stdout).map_err(Error::InvalidServerCapabilities)
+}
+
+impl zkVM for EreNativezkVM {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        zkVM::execute_with_options(&self.remote, input, options)
+    }
+
+    fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        zkVM::prove_with_options(&self.remote, input, proof_kind, options)
+    }
+
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        zkVM::verify(&self.remote, proof)
+    }
+
+    fn name(&self) -> &'static str {
+        self.zkvm.as_str()
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        self.zkvm.sdk_version()
+    }
+}
+
+impl AsynczkVM for EreNativezkVM {
+    async fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        AsynczkVM::execute_with_options(&self.remote, input, options).await
+    }
+
+    async fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        AsynczkVM::prove_with_options(&self.remote, input, proof_kind, options).await
+    }
+
+    async fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        AsynczkVM::verify(&self.remote, proof).await
+    }
+}
+
+impl zkVMProgramDigest for EreNativezkVM {
+    /// Program digest of the zkVM in server, serialized by
+    /// [`zkVMProgramDigest::program_digest_bytes`].
+    type ProgramDigest = Vec<u8>;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        self.remote.program_digest()
+    }
+
+    fn program_digest_bytes(&self) -> Result<Vec<u8>, zkVMError> {
+        self.remote.program_digest_bytes()
+    }
+}
@@ -0,0 +1,94 @@
+use crate::docker::DockerRunCmd;
+use std::path::PathBuf;
+
+/// Resource limits and placement options of the server container spawned by
+/// [`EreDockerizedzkVM`], so several provers can share a host without one
+/// exhausting the others.
+///
+/// The options are applied after the zkVM specific ones, so the environment
+/// variables set here take precedence.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use ere_dockerized::DockerizedOptions;
+///
+/// let options = DockerizedOptions::default()
+///     .with_cpuset_cpus("0-7")
+///     .with_memory("64g")
+///     .with_env("RUST_LOG", "info")
+///     .with_label("team", "prover");
+/// ```
+///
+/// [`EreDockerizedzkVM`]: crate::EreDockerizedzkVM
+#[derive(Clone, Debug, Default)]
+pub struct DockerizedOptions {
+    /// Number of CPUs the container can use (`--cpus`), e.g. `1.5`.
+    pub cpus: Option<f64>,
+    /// CPUs the container is pinned to (`--cpuset-cpus`), e.g. `0-3` or
+    /// `0,2`.
+    pub cpuset_cpus: Option<String>,
+    /// Memory limit of the container (`--memory`), e.g. `32g`.
+    pub memory: Option<String>,
+    /// Extra environment variables (`--env`).
+    pub envs: Vec<(String, String)>,
+    /// Extra volumes as pairs of host and container paths (`--volume`).
+    pub volumes: Vec<(PathBuf, PathBuf)>,
+    /// Labels of the container (`--label`).
+    pub labels: Vec<(String, String)>,
+}
+
+impl DockerizedOptions {
+    pub fn with_cpus(mut self, cpus: f64) -> Self {
+        self.cpus = Some(cpus);
+        self
+    }
+
+    pub fn with_cpuset_cpus(mut self, cpuset_cpus: impl Into<String>) -> Self {
+        self.cpuset_cpus = Some(cpuset_cpus.into());
+        self
+    }
+
+    pub fn with_memory(mut self, memory: impl Into<String>) -> Self {
+        self.memory = Some(memory.into());
+        self
+    }
+
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_volume(mut self, host: impl Into<PathBuf>, container: impl Into<PathBuf>) -> Self {
+        self.volumes.push((host.into(), container.into()));
+        self
+    }
+
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.push((key.into(), value.into()));
+        self
+    }
+
+    /// Applies the options to the `docker run` command.
+    pub(crate) fn apply(&self, mut cmd: DockerRunCmd) -> DockerRunCmd {
+        if let Some(cpus) = self.cpus {
+            cmd = cmd.cpus(cpus);
+        }
+        if let Some(cpuset_cpus) = &self.cpuset_cpus {
+            cmd = cmd.cpuset_cpus(cpuset_cpus);
+        }
+        if let Some(memory) = &self.memory {
+            cmd = cmd.memory(memory);
+        }
+        for (key, value) in &self.envs {
+            cmd = cmd.env(key, value);
+        }
+        for (host, container) in &self.volumes {
+            cmd = cmd.volume(host, container);
+        }
+        for (key, value) in &self.labels {
+            cmd = cmd.label(key, value);
+        }
+        cmd
+    }
+}
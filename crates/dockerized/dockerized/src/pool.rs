@@ -0,0 +1,252 @@
+use crate::{
+    DockerizedOptions, EreDockerizedzkVM, ErezkVM, SerializedProgram, block_on, error::Error,
+};
+use ere_zkvm_interface::zkvm::ProverResourceType;
+use std::{
+    collections::HashMap,
+    mem,
+    ops::{Deref, DerefMut},
+    sync::Mutex,
+};
+
+/// Default maximum number of idle servers kept per zkVM.
+pub const DEFAULT_MAX_IDLE_PER_ZKVM: usize = 4;
+
+/// Pool of warm server containers per zkVM.
+///
+/// Servers are handed out as [`PooledzkVM`] with the requested program loaded
+/// by the `LoadProgram` RPC, so switching programs skips the container start,
+/// though the zkVM is still set up for the new program. A server is returned
+/// to the pool when the [`PooledzkVM`] is dropped, unless its container has
+/// exited or the pool is full, in which case it is stopped.
+///
+/// A server dropped with queued or running jobs is kept running out of the
+/// idle ones, and returned to them once its jobs are finished, checked when a
+/// server is taken from or added to the pool. Servers are only stopped with
+/// their jobs when the pool itself is dropped.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ere_dockerized::{EreDockerizedCompiler, EreDockerizedPool, ErezkVM};
+/// use ere_zkvm_interface::{
+///     compiler::Compiler,
+///     zkvm::{ProverResourceType, zkVM},
+/// };
+/// use std::path::Path;
+///
+/// let compiler = EreDockerizedCompiler::new(ErezkVM::SP1, "mounting/directory")?;
+/// let program_a = compiler.compile(Path::new("relative/path/to/guest/a"))?;
+/// let program_b = compiler.compile(Path::new("relative/path/to/guest/b"))?;
+///
+/// let pool = EreDockerizedPool::new(ProverResourceType::Cpu);
+/// pool.warm_up(ErezkVM::SP1, &program_a, 2)?;
+///
+/// let zkvm = pool.get(ErezkVM::SP1, program_b)?;
+/// let (public_values, report) = zkvm.execute(&[])?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EreDockerizedPool {
+    resource: ProverResourceType,
+    options: DockerizedOptions,
+    max_idle_per_zkvm: usize,
+    idle: Mutex<HashMap<ErezkVM, Vec<EreDockerizedzkVM>>>,
+    /// Servers dropped with pending jobs, waiting for them to finish.
+    draining: Mutex<Vec<EreDockerizedzkVM>>,
+}
+
+impl EreDockerizedPool {
+    pub fn new(resource: ProverResourceType) -> Self {
+        Self {
+            resource,
+            options: DockerizedOptions::default(),
+            max_idle_per_zkvm: DEFAULT_MAX_IDLE_PER_ZKVM,
+            idle: Mutex::new(HashMap::new()),
+            draining: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the options of the server containers spawned by the pool.
+    pub fn with_options(mut self, options: DockerizedOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the maximum number of idle servers kept per zkVM.
+    pub fn with_max_idle_per_zkvm(mut self, max_idle_per_zkvm: usize) -> Self {
+        self.max_idle_per_zkvm = max_idle_per_zkvm;
+        self
+    }
+
+    pub fn resource(&self) -> &ProverResourceType {
+        &self.resource
+    }
+
+    pub fn options(&self) -> &DockerizedOptions {
+        &self.options
+    }
+
+    /// Returns the number of servers kept running for their pending jobs.
+    pub fn num_draining(&self) -> usize {
+        self.draining.lock().unwrap().len()
+    }
+
+    /// Returns the number of idle servers of `zkvm`.
+    pub fn num_idle(&self, zkvm: ErezkVM) -> usize {
+        self.idle
+            .lock()
+            .unwrap()
+            .get(&zkvm)
+            .map_or(0, |servers| servers.len())
+    }
+
+    /// Returns a server of `zkvm` with `program` loaded, reusing an idle one
+    /// if any, otherwise spawning a new one.
+    pub fn get(&self, zkvm: ErezkVM, program: SerializedProgram) -> Result<PooledzkVM<'_>, Error> {
+        block_on(self.get_async(zkvm, program))
+    }
+
+    /// Same as [`EreDockerizedPool::get`] but doesn't block the async runtime.
+    pub async fn get_async(
+        &self,
+        zkvm: ErezkVM,
+        program: SerializedProgram,
+    ) -> Result<PooledzkVM<'_>, Error> {
+        self.reclaim_drained().await;
+        let server = match self.take_idle(zkvm, &program) {
+            Some(mut server) => {
+                if server.program() != &program {
+                    server.load_program_async(program).await?;
+                }
+                server
+            }
+            None => self.spawn(zkvm, program).await?,
+        };
+        Ok(PooledzkVM {
+            pool: self,
+            server: Some(server),
+        })
+    }
+
+    /// Spawns servers of `zkvm` with `program` loaded until there are `count`
+    /// idle ones, bounded by the maximum number of idle servers.
+    pub fn warm_up(
+        &self,
+        zkvm: ErezkVM,
+        program: &SerializedProgram,
+        count: usize,
+    ) -> Result<(), Error> {
+        block_on(self.warm_up_async(zkvm, program, count))
+    }
+
+    /// Same as [`EreDockerizedPool::warm_up`] but doesn't block the async
+    /// runtime.
+    pub async fn warm_up_async(
+        &self,
+        zkvm: ErezkVM,
+        program: &SerializedProgram,
+        count: usize,
+    ) -> Result<(), Error> {
+        self.reclaim_drained().await;
+        let count = count.min(self.max_idle_per_zkvm);
+        while self.num_idle(zkvm) < count {
+            let server = self.spawn(zkvm, program.clone()).await?;
+            self.put_back(server);
+        }
+        Ok(())
+    }
+
+    async fn spawn(
+        &self,
+        zkvm: ErezkVM,
+        program: SerializedProgram,
+    ) -> Result<EreDockerizedzkVM, Error> {
+        EreDockerizedzkVM::new_async_with_options(
+            zkvm,
+            program,
+            self.resource.clone(),
+            self.options.clone(),
+        )
+        .await
+    }
+
+    /// Takes an idle server of `zkvm`, preferring one with `program` already
+    /// loaded. Servers whose container has exited are dropped.
+    fn take_idle(&self, zkvm: ErezkVM, program: &SerializedProgram) -> Option<EreDockerizedzkVM> {
+        let mut idle = self.idle.lock().unwrap();
+        let servers = idle.get_mut(&zkvm)?;
+        servers.retain(|server| !server.is_server_exited());
+        let idx = servers
+            .iter()
+            .position(|server| server.program() == program)
+            .or_else(|| servers.len().checked_sub(1))?;
+        Some(servers.swap_remove(idx))
+    }
+
+    /// Returns the draining servers whose jobs are finished to the idle ones,
+    /// servers whose container has exited are dropped.
+    async fn reclaim_drained(&self) {
+        let draining = mem::take(&mut *self.draining.lock().unwrap());
+        for server in draining {
+            if server.is_server_exited() {
+                continue;
+            }
+            if server.has_pending_jobs_async().await {
+                self.draining.lock().unwrap().push(server);
+            } else {
+                self.put_back(server);
+            }
+        }
+    }
+
+    fn put_back(&self, server: EreDockerizedzkVM) {
+        if server.is_server_exited() {
+            return;
+        }
+        let mut idle = self.idle.lock().unwrap();
+        let servers = idle.entry(server.zkvm()).or_default();
+        if servers.len() < self.max_idle_per_zkvm {
+            servers.push(server);
+        }
+    }
+}
+
+/// Server handed out by [`EreDockerizedPool`], which is returned to the pool
+/// when dropped.
+///
+/// If it still has queued or running jobs, it's neither handed out again nor
+/// stopped, so the jobs keep running and their results can be fetched by job
+/// id from another client. It's returned to the idle servers once the jobs are
+/// finished.
+pub struct PooledzkVM<'a> {
+    pool: &'a EreDockerizedPool,
+    server: Option<EreDockerizedzkVM>,
+}
+
+impl Deref for PooledzkVM<'_> {
+    type Target = EreDockerizedzkVM;
+
+    fn deref(&self) -> &Self::Target {
+        self.server.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledzkVM<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.server.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledzkVM<'_> {
+    fn drop(&mut self) {
+        if let Some(server) = self.server.take() {
+            if server.has_pending_jobs() {
+                self.pool.draining.lock().unwrap().push(server);
+            } else {
+                self.pool.put_back(server);
+            }
+        }
+    }
+}
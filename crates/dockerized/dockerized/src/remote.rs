@@ -0,0 +1,198 @@
+use crate::{ErezkVM, SerializedProgram, block_on, error::Error};
+use ere_server::client::{ConnectOptions, ServerInfo, Url, zkVMClient};
+use ere_zkvm_interface::zkvm::{
+    AsynczkVM, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
+    ProveOptions, PublicValues, zkVM, zkVMError, zkVMProgramDigest,
+};
+
+/// zkVM that forwards the methods to an already-running `ere-server`, e.g. on
+/// a dedicated prover machine.
+///
+/// Unlike [`EreDockerizedzkVM`], it doesn't build any image nor spawn any
+/// container, the program and prover resource are the ones the server is
+/// started with.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ere_dockerized::{ConnectOptions, EreRemotezkVM, Url};
+/// use ere_zkvm_interface::zkvm::{ProofKind, zkVM};
+/// use std::time::Duration;
+///
+/// let url = Url::parse("http://prover-0:3000")?;
+/// let options = ConnectOptions::default()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_connect_timeout(Duration::from_secs(5));
+/// let zkvm = EreRemotezkVM::connect_with_options(url, &options)?;
+///
+/// let (public_values, proof, report) = zkvm.prove(&[], ProofKind::Compressed)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`EreDockerizedzkVM`]: crate::EreDockerizedzkVM
+pub struct EreRemotezkVM {
+    zkvm: ErezkVM,
+    url: Url,
+    info: ServerInfo,
+    /// SDK version reported by the server, leaked once per connection since
+    /// [`zkVM::sdk_version`] returns `&'static str`.
+    sdk_version: &'static str,
+    client: zkVMClient,
+}
+
+impl EreRemotezkVM {
+    /// Same as [`EreRemotezkVM::connect_with_options`] with the default
+    /// [`ConnectOptions`].
+    pub fn connect(url: Url) -> Result<Self, Error> {
+        Self::connect_with_options(url, &ConnectOptions::default())
+    }
+
+    /// Connects to the server at `url`, retrying until it's healthy as
+    /// configured by `options`.
+    ///
+    /// Returns [`Error::ServerVersionMismatch`] if the server speaks another
+    /// protocol version, unless [`ConnectOptions::skip_version_check`] is set.
+    pub fn connect_with_options(url: Url, options: &ConnectOptions) -> Result<Self, Error> {
+        block_on(Self::connect_async(url, options))
+    }
+
+    /// Same as [`EreRemotezkVM::connect_with_options`] but doesn't block the
+    /// async runtime.
+    pub async fn connect_async(url: Url, options: &ConnectOptions) -> Result<Self, Error> {
+        let client = zkVMClient::connect(url.clone(), options).await?;
+        let info = client.info().await?;
+        let zkvm = info
+            .name
+            .parse()
+            .map_err(|_| Error::UnsupportedServerzkVM(info.name.clone()))?;
+        let sdk_version = Box::leak(info.sdk_version.clone().into_boxed_str());
+        Ok(Self {
+            zkvm,
+            url,
+            info,
+            sdk_version,
+            client,
+        })
+    }
+
+    pub fn zkvm(&self) -> ErezkVM {
+        self.zkvm
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns [`ServerInfo`] of the server returned when connected.
+    pub fn info(&self) -> &ServerInfo {
+        &self.info
+    }
+
+    /// Returns the underlying [`zkVMClient`], e.g. to submit prove jobs.
+    pub fn client(&self) -> &zkVMClient {
+        &self.client
+    }
+
+    /// Replaces the program of the server without restarting it. The server
+    /// rejects it while any request or job is pending, since the previous
+    /// zkVM is dropped before setting up the new one.
+    pub fn load_program(&mut self, program: &SerializedProgram) -> Result<(), Error> {
+        block_on(self.load_program_async(program))
+    }
+
+    /// Same as [`EreRemotezkVM::load_program`] but doesn't block the async
+    /// runtime.
+    pub async fn load_program_async(&mut self, program: &SerializedProgram) -> Result<(), Error> {
+        let program_digest = self.client.load_program(program.0.clone()).await?;
+        self.info.program_digest = program_digest;
+        Ok(())
+    }
+}
+
+impl zkVM for EreRemotezkVM {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        let (public_values, report) =
+            block_on(self.client.execute(input.to_vec(), options)).map_err(zkVMError::from)?;
+
+        Ok((public_values, report))
+    }
+
+    fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        let (public_values, proof, report) =
+            block_on(self.client.prove(input.to_vec(), proof_kind, options))
+                .map_err(zkVMError::from)?;
+
+        Ok((public_values, proof, report))
+    }
+
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        let public_values = block_on(self.client.verify(proof)).map_err(zkVMError::from)?;
+
+        Ok(public_values)
+    }
+
+    fn name(&self) -> &'static str {
+        self.zkvm.as_str()
+    }
+
+    /// Returns the SDK version reported by the server when connected, which
+    /// could differ from the one the host is built with.
+    fn sdk_version(&self) -> &'static str {
+        self.sdk_version
+    }
+}
+
+impl AsynczkVM for EreRemotezkVM {
+    async fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        self.client.execute_with_options(input, options).await
+    }
+
+    async fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        self.client
+            .prove_with_options(input, proof_kind, options)
+            .await
+    }
+
+    async fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        AsynczkVM::verify(&self.client, proof).await
+    }
+}
+
+impl zkVMProgramDigest for EreRemotezkVM {
+    /// Program digest of the zkVM in server, serialized by
+    /// [`zkVMProgramDigest::program_digest_bytes`].
+    type ProgramDigest = Vec<u8>;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        self.program_digest_bytes()
+    }
+
+    /// Returns the program digest bytes as is, so it's the same as the one
+    /// returned by the zkVM in server.
+    fn program_digest_bytes(&self) -> Result<Vec<u8>, zkVMError> {
+        let program_digest =
+            block_on(self.client.program_digest_bytes()).map_err(zkVMError::from)?;
+
+        Ok(program_digest)
+    }
+}
@@ -1,8 +1,24 @@
+use ere_build_utils::detect_self_crate_version;
+use std::{env, fs, path::Path};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    generate_crate_version();
     prost_build::Config::new()
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]") // enable support for JSON encoding
         .service_generator(twirp_build::service_generator())
         .compile_protos(&["./proto/api.proto"], &["./proto"])
         .expect("error compiling protos");
+    println!("cargo:rerun-if-changed=Cargo.lock");
     Ok(())
 }
+
+fn generate_crate_version() {
+    let crate_version = format!(
+        "const CRATE_VERSION: &str = \"{}\";",
+        detect_self_crate_version()
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dst = Path::new(&out_dir).join("crate_version.rs");
+    fs::write(dst, crate_version).unwrap();
+}
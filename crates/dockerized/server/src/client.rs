@@ -1,14 +1,29 @@
-use crate::api::{
-    ExecuteRequest, ProveRequest, VerifyRequest, ZkvmService,
-    execute_response::Result as ExecuteResult, prove_response::Result as ProveResult,
-    verify_response::Result as VerifyResult,
+use crate::{
+    PROTOCOL_VERSION,
+    api::{
+        self, CancelRequest, ErrorCode, ExecuteRequest, GetJobRequest, GetResultRequest,
+        InfoRequest, ListJobsRequest, LoadProgramRequest, ProgramDigestRequest, ProveOk,
+        ProveRequest, ProveStatusRequest, VerifyRequest, ZkvmError, ZkvmService,
+        execute_response::Result as ExecuteResult, get_result_response::Result as GetResultResult,
+        load_program_response::Result as LoadProgramResult,
+        program_digest_response::Result as ProgramDigestResult,
+        prove_response::Result as ProveResult, verify_response::Result as VerifyResult,
+    },
 };
 use ere_zkvm_interface::zkvm::{
-    ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind, PublicValues,
+    AsynczkVM, CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProveProgress, ProverResourceKind, PublicValues,
+    SegmentProgress, zkVMCapabilities, zkVMError,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    pin::pin,
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant, SystemTime},
 };
-use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 use twirp::{Client, Request, reqwest};
 
 pub use twirp::{TwirpErrorResponse, url::Url};
@@ -17,13 +32,183 @@ pub use twirp::{TwirpErrorResponse, url::Url};
 #[allow(non_camel_case_types)]
 pub enum Error {
     #[error("zkVM method error: {0}")]
-    zkVM(String),
-    #[error("Connection to zkVM server timeout after 5 minutes")]
-    ConnectionTimeout,
+    zkVM(zkVMError),
+    #[error("zkVM server is not healthy after {attempts} attempts in {elapsed:?}")]
+    ConnectionTimeout { attempts: u32, elapsed: Duration },
+    #[error(
+        "Protocol version mismatch, client: {client}, server: {server} (server version {server_version})"
+    )]
+    VersionMismatch {
+        client: u32,
+        server: u32,
+        server_version: String,
+    },
     #[error("RPC error: {0}")]
     Rpc(#[from] TwirpErrorResponse),
 }
 
+/// Options of [`zkVMClient::connect`] to wait for the server to be healthy.
+#[derive(Clone, Debug)]
+pub struct ConnectOptions {
+    /// Maximum time to wait for the server to be healthy, defaults to 5
+    /// minutes.
+    pub timeout: Duration,
+    /// Maximum number of health checks after the first one, `None` to retry
+    /// until the timeout.
+    pub max_retries: Option<u32>,
+    /// Interval between health checks, defaults to 500 milliseconds.
+    pub retry_interval: Duration,
+    /// Timeout to establish each connection to the server, also applies to
+    /// the requests after connected. `None` to use the OS default.
+    pub connect_timeout: Option<Duration>,
+    /// Whether to skip checking the protocol version of the server, e.g. to
+    /// talk to a server known to be compatible. Defaults to `false`.
+    pub skip_version_check: bool,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(300),
+            max_retries: None,
+            retry_interval: Duration::from_millis(500),
+            connect_timeout: None,
+            skip_version_check: false,
+        }
+    }
+}
+
+impl ConnectOptions {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    pub fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn with_skip_version_check(mut self, skip_version_check: bool) -> Self {
+        self.skip_version_check = skip_version_check;
+        self
+    }
+}
+
+/// Status of an in-flight proving returned by [`zkVMClient::prove_status`].
+#[derive(Clone, Debug)]
+pub struct ProvingStatus {
+    pub request_id: Option<String>,
+    /// `None` if the zkVM hasn't reported any phase yet.
+    pub phase: Option<ProvePhase>,
+    /// `None` if the zkVM is not proving the segments or doesn't report them.
+    pub segments: Option<SegmentProgress>,
+    pub elapsed: Duration,
+}
+
+impl ProvingStatus {
+    /// Returns the progress reported by the zkVM, `None` if it hasn't
+    /// reported any phase yet.
+    pub fn progress(&self) -> Option<ProveProgress> {
+        Some(ProveProgress {
+            phase: self.phase?,
+            segments: self.segments,
+        })
+    }
+}
+
+/// Identity, version and capabilities of the server returned by
+/// [`zkVMClient::info`].
+#[derive(Clone, Debug)]
+pub struct ServerInfo {
+    /// `zkVM::name` of the zkVM in server.
+    pub name: String,
+    /// `zkVM::sdk_version` of the zkVM in server.
+    pub sdk_version: String,
+    /// Version of `ere-server` with git revision.
+    pub server_version: String,
+    /// [`PROTOCOL_VERSION`] of the server.
+    pub protocol_version: u32,
+    /// `None` if the zkVM in server fails to return the program digest.
+    pub program_digest: Option<Vec<u8>>,
+    pub supported_proof_kinds: Vec<ProofKind>,
+    pub resource: ProverResourceKind,
+    pub supported_prover_resource_kinds: Vec<ProverResourceKind>,
+}
+
+/// Capabilities of the zkVM in `ere-server`, printed as JSON by
+/// `ere-server --capabilities`, so they can be checked without spawning the
+/// server with a program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerCapabilities {
+    pub supported_prover_resource_kinds: Vec<ProverResourceKind>,
+    pub supported_proof_kinds: Vec<ProofKind>,
+}
+
+impl ServerCapabilities {
+    /// Returns the capabilities from the [`zkVMCapabilities`] consts.
+    pub fn of<T: zkVMCapabilities>() -> Self {
+        Self {
+            supported_prover_resource_kinds: T::SUPPORTED_PROVER_RESOURCE_KINDS.to_vec(),
+            supported_proof_kinds: T::SUPPORTED_PROOF_KINDS.to_vec(),
+        }
+    }
+
+    /// Returns `Err` if the [`ProverResourceKind`] is not supported.
+    pub fn check_prover_resource(&self, resource: ProverResourceKind) -> Result<(), CommonError> {
+        if !self.supported_prover_resource_kinds.contains(&resource) {
+            return Err(CommonError::unsupported_prover_resource_kind(
+                resource,
+                self.supported_prover_resource_kinds.iter().copied(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// State of a job submitted by [`zkVMClient::submit_prove`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    /// Finished, the result can be fetched by [`zkVMClient::get_result`].
+    Succeeded,
+    /// Finished with error (including cancellation), which is returned by
+    /// [`zkVMClient::get_result`].
+    Failed,
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed)
+    }
+}
+
+/// Status of a job returned by [`zkVMClient::get_job`].
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub job_id: String,
+    pub state: JobState,
+    /// `None` if the job is not running or the zkVM hasn't reported any phase
+    /// yet.
+    pub phase: Option<ProvePhase>,
+    /// `None` if the job is not proving the segments or the zkVM doesn't
+    /// report them.
+    pub segments: Option<SegmentProgress>,
+    /// Time since the submission, until the job is finished.
+    pub elapsed: Duration,
+}
+
 /// zkVM client of the `zkVMServer`.
 #[allow(non_camel_case_types)]
 pub struct zkVMClient {
@@ -31,34 +216,104 @@ pub struct zkVMClient {
 }
 
 impl zkVMClient {
+    /// Same as [`zkVMClient::connect`] with the default [`ConnectOptions`].
     pub async fn new(url: Url) -> Result<Self, Error> {
-        const TIMEOUT: Duration = Duration::from_secs(300); // 5mins
-        const INTERVAL: Duration = Duration::from_millis(500);
+        Self::connect(url, &ConnectOptions::default()).await
+    }
 
-        let http_client = reqwest::Client::new();
+    /// Waits for the server to be healthy, then returns
+    /// [`Error::VersionMismatch`] if the server speaks another
+    /// [`PROTOCOL_VERSION`], unless [`ConnectOptions::skip_version_check`] is
+    /// set.
+    pub async fn connect(url: Url, options: &ConnectOptions) -> Result<Self, Error> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(connect_timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        let http_client = builder
+            .build()
+            .map_err(|err| twirp::internal(format!("failed to build HTTP client: {err}")))?;
 
         let start = Instant::now();
+        let mut attempts = 0;
         loop {
-            if start.elapsed() > TIMEOUT {
-                return Err(Error::ConnectionTimeout);
+            attempts += 1;
+
+            let remaining = options.timeout.saturating_sub(start.elapsed());
+            let health = http_client
+                .get(url.join("health").unwrap())
+                .timeout(remaining)
+                .send()
+                .await;
+            if matches!(health, Ok(response) if response.status().is_success()) {
+                break;
             }
 
-            match http_client.get(url.join("health").unwrap()).send().await {
-                Ok(response) if response.status().is_success() => break,
-                _ => sleep(INTERVAL).await,
+            let retries_exhausted = options.max_retries.is_some_and(|max| attempts > max);
+            if retries_exhausted || start.elapsed() + options.retry_interval > options.timeout {
+                return Err(Error::ConnectionTimeout {
+                    attempts,
+                    elapsed: start.elapsed(),
+                });
             }
+            sleep(options.retry_interval).await;
         }
 
         let client = Client::new(url.join("twirp").unwrap(), http_client, Vec::new(), None);
+        let client = Self { client };
+
+        let info = client.info().await?;
+        if !options.skip_version_check && info.protocol_version != PROTOCOL_VERSION {
+            return Err(Error::VersionMismatch {
+                client: PROTOCOL_VERSION,
+                server: info.protocol_version,
+                server_version: info.server_version,
+            });
+        }
 
-        Ok(Self { client })
+        Ok(client)
+    }
+
+    pub async fn info(&self) -> Result<ServerInfo, Error> {
+        let request = Request::new(InfoRequest {});
+
+        let response = self.client.info(request).await?.into_body();
+
+        let resource = resource_kind(response.resource).ok_or_else(|| {
+            twirp::internal(format!(
+                "invalid prover resource kind: {}",
+                response.resource
+            ))
+        })?;
+        Ok(ServerInfo {
+            name: response.name,
+            sdk_version: response.sdk_version,
+            server_version: response.server_version,
+            protocol_version: response.protocol_version,
+            program_digest: response.program_digest,
+            supported_proof_kinds: response
+                .supported_proof_kinds
+                .into_iter()
+                .filter_map(|proof_kind| ProofKind::from_repr(proof_kind as usize))
+                .collect(),
+            resource,
+            supported_prover_resource_kinds: response
+                .supported_prover_resource_kinds
+                .into_iter()
+                .filter_map(resource_kind)
+                .collect(),
+        })
     }
 
     pub async fn execute(
         &self,
         input: Vec<u8>,
+        options: &ExecuteOptions,
     ) -> Result<(PublicValues, ProgramExecutionReport), Error> {
-        let request = Request::new(ExecuteRequest { input });
+        let request = Request::new(ExecuteRequest {
+            input,
+            cycle_limit: options.cycle_limit,
+        });
 
         let response = self.client.execute(request).await?;
 
@@ -69,34 +324,163 @@ impl zkVMClient {
                     .map_err(deserialize_report_err)?
                     .0,
             )),
-            ExecuteResult::Err(err) => Err(Error::zkVM(err)),
+            ExecuteResult::Err(err) => Err(zkvm_err(err)),
         }
     }
 
+    /// Sends prove request to the server.
+    ///
+    /// If [`ProveOptions::cancellation_token`] or [`ProveOptions::progress`] is
+    /// set, it polls the token and the proving status meanwhile, and sends
+    /// `Cancel` request once the token is cancelled.
     pub async fn prove(
         &self,
         input: Vec<u8>,
         proof_kind: ProofKind,
+        options: &ProveOptions,
     ) -> Result<(PublicValues, Proof, ProgramProvingReport), Error> {
-        let request = Request::new(ProveRequest {
+        // Interval to poll the cancellation token and the proving status.
+        const INTERVAL: Duration = Duration::from_millis(500);
+
+        let request_id = new_request_id();
+        let request = Request::new(prove_request(
             input,
-            proof_kind: proof_kind as i32,
-        });
+            proof_kind,
+            options,
+            Some(request_id.clone()),
+        ));
 
-        let response = self.client.prove(request).await?;
+        let response = if options.cancellation_token.is_none() && options.progress.is_none() {
+            self.client.prove(request).await?
+        } else {
+            let mut response = pin!(self.client.prove(request));
+            let (mut cancelled, mut progress) = (false, None);
+            loop {
+                if let Ok(response) = timeout(INTERVAL, &mut response).await {
+                    break response?;
+                }
+
+                let token = options.cancellation_token.as_ref();
+                if !cancelled && token.is_some_and(|token| token.is_cancelled()) {
+                    self.cancel(Some(request_id.clone())).await?;
+                    cancelled = true;
+                }
+
+                // Failing to get the status doesn't fail the proving.
+                if let Some(callback) = &options.progress {
+                    let status = self.prove_status(Some(request_id.clone())).await;
+                    let current = status
+                        .ok()
+                        .and_then(|status| status.into_iter().next()?.progress());
+                    match current {
+                        Some(current) if Some(current) != progress => {
+                            progress = Some(current);
+                            callback.report(current);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        };
 
         match response.into_body().result.ok_or_else(result_none_err)? {
-            ProveResult::Ok(result) => Ok((
-                result.public_values,
-                Proof::new(proof_kind, result.proof),
-                bincode::serde::decode_from_slice(&result.report, bincode::config::legacy())
-                    .map_err(deserialize_report_err)?
-                    .0,
-            )),
-            ProveResult::Err(err) => Err(Error::zkVM(err)),
+            ProveResult::Ok(result) => prove_ok(result, proof_kind),
+            ProveResult::Err(err) => Err(zkvm_err(err)),
         }
     }
 
+    /// Submits prove request to the job queue of the server and returns the
+    /// job id without waiting for the proving.
+    ///
+    /// The job can be polled by [`zkVMClient::get_job`], and cancelled by
+    /// [`zkVMClient::cancel`] with the job id. [`ProveOptions::cancellation_token`]
+    /// and [`ProveOptions::progress`] are ignored.
+    pub async fn submit_prove(
+        &self,
+        input: Vec<u8>,
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<String, Error> {
+        let request = Request::new(prove_request(input, proof_kind, options, None));
+
+        let response = self.client.submit_prove(request).await?;
+
+        Ok(response.into_body().job_id)
+    }
+
+    pub async fn get_job(&self, job_id: String) -> Result<Job, Error> {
+        let request = Request::new(GetJobRequest { job_id });
+
+        let response = self.client.get_job(request).await?;
+
+        job(response.into_body().job.ok_or_else(result_none_err)?)
+    }
+
+    /// Returns result of a finished job, or [`Error::Rpc`] if the job is not
+    /// finished yet.
+    pub async fn get_result(
+        &self,
+        job_id: String,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), Error> {
+        let request = Request::new(GetResultRequest { job_id });
+
+        let response = self.client.get_result(request).await?.into_body();
+
+        let proof_kind = ProofKind::from_repr(response.proof_kind as usize).ok_or_else(|| {
+            twirp::internal(format!("invalid proof kind: {}", response.proof_kind))
+        })?;
+        match response.result.ok_or_else(result_none_err)? {
+            GetResultResult::Ok(result) => prove_ok(result, proof_kind),
+            GetResultResult::Err(err) => Err(zkvm_err(err)),
+        }
+    }
+
+    /// Returns status of the queued, running and the most recently finished
+    /// jobs of the server, in submission order.
+    pub async fn list_jobs(&self) -> Result<Vec<Job>, Error> {
+        let request = Request::new(ListJobsRequest {});
+
+        let response = self.client.list_jobs(request).await?;
+
+        response.into_body().jobs.into_iter().map(job).collect()
+    }
+
+    /// Cancels in-flight provings and queued jobs of the server with the
+    /// `request_id` (or job id), or all if it's `None`. Returns the number of
+    /// provings and jobs cancelled.
+    pub async fn cancel(&self, request_id: Option<String>) -> Result<u32, Error> {
+        let request = Request::new(CancelRequest { request_id });
+
+        let response = self.client.cancel(request).await?;
+
+        Ok(response.into_body().num_cancelled)
+    }
+
+    /// Returns status of in-flight provings of the server with the
+    /// `request_id`, or all if it's `None`.
+    pub async fn prove_status(
+        &self,
+        request_id: Option<String>,
+    ) -> Result<Vec<ProvingStatus>, Error> {
+        let request = Request::new(ProveStatusRequest { request_id });
+
+        let response = self.client.prove_status(request).await?;
+
+        Ok(response
+            .into_body()
+            .provings
+            .into_iter()
+            .map(|status| ProvingStatus {
+                request_id: status.request_id,
+                phase: status
+                    .phase
+                    .and_then(|phase| ProvePhase::from_repr(phase as usize)),
+                segments: status.segments.map(segment_progress),
+                elapsed: Duration::from_millis(status.elapsed_ms),
+            })
+            .collect())
+    }
+
     pub async fn verify(&self, proof: &Proof) -> Result<PublicValues, Error> {
         let request = Request::new(VerifyRequest {
             proof: proof.as_bytes().to_vec(),
@@ -107,9 +491,165 @@ impl zkVMClient {
 
         match response.into_body().result.ok_or_else(result_none_err)? {
             VerifyResult::Ok(result) => Ok(result.public_values),
-            VerifyResult::Err(err) => Err(Error::zkVM(err)),
+            VerifyResult::Err(err) => Err(zkvm_err(err)),
         }
     }
+
+    /// Returns the program digest bytes returned by
+    /// `zkVMProgramDigest::program_digest_bytes` of the zkVM in server.
+    pub async fn program_digest_bytes(&self) -> Result<Vec<u8>, Error> {
+        let request = Request::new(ProgramDigestRequest {});
+
+        let response = self.client.program_digest(request).await?;
+
+        match response.into_body().result.ok_or_else(result_none_err)? {
+            ProgramDigestResult::Ok(result) => Ok(result.program_digest),
+            ProgramDigestResult::Err(err) => Err(zkvm_err(err)),
+        }
+    }
+
+    /// Replaces the program of the server with the serialized one, and
+    /// returns its program digest if the zkVM in server returns one.
+    ///
+    /// The server rejects it with `failed_precondition` while any request or
+    /// job is pending, since the previous zkVM is dropped before setting up
+    /// the new one. If setting up fails, the server has no program loaded
+    /// until another one is loaded successfully.
+    pub async fn load_program(&self, program: Vec<u8>) -> Result<Option<Vec<u8>>, Error> {
+        let request = Request::new(LoadProgramRequest { program });
+
+        let response = self.client.load_program(request).await?;
+
+        match response.into_body().result.ok_or_else(result_none_err)? {
+            LoadProgramResult::Ok(result) => Ok(result.program_digest),
+            LoadProgramResult::Err(err) => Err(zkvm_err(err)),
+        }
+    }
+}
+
+impl AsynczkVM for zkVMClient {
+    async fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        Ok(self.execute(input.to_vec(), options).await?)
+    }
+
+    async fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Ok(self.prove(input.to_vec(), proof_kind, options).await?)
+    }
+
+    async fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        Ok(zkVMClient::verify(self, proof).await?)
+    }
+}
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::zkVM(err) => err,
+            Error::ConnectionTimeout { .. } | Error::VersionMismatch { .. } | Error::Rpc(_) => {
+                zkVMError::infrastructure(err)
+            }
+        }
+    }
+}
+
+/// Converts [`ZkvmError`] back to [`zkVMError`].
+fn zkvm_err(err: ZkvmError) -> Error {
+    let code = ErrorCode::try_from(err.code).unwrap_or(ErrorCode::Infrastructure);
+    let message = err.message;
+    Error::zkVM(match code {
+        ErrorCode::GuestPanic => zkVMError::guest_panic(message, err.exit_code),
+        ErrorCode::InvalidInput => zkVMError::InvalidInput(anyhow::Error::msg(message)),
+        ErrorCode::CycleLimitExceeded => zkVMError::CycleLimitExceeded {
+            cycle_limit: err.cycle_limit.unwrap_or_default(),
+        },
+        ErrorCode::InvalidProof => zkVMError::InvalidProof(anyhow::Error::msg(message)),
+        ErrorCode::Unsupported => zkVMError::Unsupported(anyhow::Error::msg(message)),
+        ErrorCode::Cancelled => zkVMError::Cancelled(anyhow::Error::msg(message)),
+        ErrorCode::Infrastructure => zkVMError::Infrastructure(anyhow::Error::msg(message)),
+    })
+}
+
+fn prove_request(
+    input: Vec<u8>,
+    proof_kind: ProofKind,
+    options: &ProveOptions,
+    request_id: Option<String>,
+) -> ProveRequest {
+    ProveRequest {
+        input,
+        proof_kind: proof_kind as i32,
+        cycle_limit: options.cycle_limit,
+        timeout_ms: options.timeout.map(|timeout| timeout.as_millis() as u64),
+        request_id,
+    }
+}
+
+fn prove_ok(
+    result: ProveOk,
+    proof_kind: ProofKind,
+) -> Result<(PublicValues, Proof, ProgramProvingReport), Error> {
+    Ok((
+        result.public_values,
+        Proof::new(proof_kind, result.proof),
+        bincode::serde::decode_from_slice(&result.report, bincode::config::legacy())
+            .map_err(deserialize_report_err)?
+            .0,
+    ))
+}
+
+fn job(job: api::Job) -> Result<Job, Error> {
+    let state = match api::JobState::try_from(job.state) {
+        Ok(api::JobState::Queued) => JobState::Queued,
+        Ok(api::JobState::Running) => JobState::Running,
+        Ok(api::JobState::Succeeded) => JobState::Succeeded,
+        Ok(api::JobState::Failed) => JobState::Failed,
+        Err(_) => return Err(twirp::internal(format!("invalid job state: {}", job.state)).into()),
+    };
+    Ok(Job {
+        job_id: job.job_id,
+        state,
+        phase: job
+            .phase
+            .and_then(|phase| ProvePhase::from_repr(phase as usize)),
+        segments: job.segments.map(segment_progress),
+        elapsed: Duration::from_millis(job.elapsed_ms),
+    })
+}
+
+fn segment_progress(segments: api::SegmentProgress) -> SegmentProgress {
+    SegmentProgress {
+        proved: segments.proved,
+        total: segments.total,
+    }
+}
+
+fn resource_kind(resource: i32) -> Option<ProverResourceKind> {
+    match api::ProverResourceKind::try_from(resource).ok()? {
+        api::ProverResourceKind::Cpu => Some(ProverResourceKind::Cpu),
+        api::ProverResourceKind::Gpu => Some(ProverResourceKind::Gpu),
+        api::ProverResourceKind::Network => Some(ProverResourceKind::Network),
+        api::ProverResourceKind::Mock => Some(ProverResourceKind::Mock),
+    }
+}
+
+/// Returns an id for prove request, unique among processes and calls.
+fn new_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{nanos:x}-{count}", process::id())
 }
 
 fn result_none_err() -> TwirpErrorResponse {
@@ -7,3 +7,14 @@ pub(crate) mod api {
 
 #[cfg(feature = "server")]
 pub mod server;
+
+#[cfg(feature = "server")]
+include!(concat!(env!("OUT_DIR"), "/crate_version.rs"));
+
+/// Version of the protocol between client and server, bumped on incompatible
+/// changes of the requests, or the encoding of reports and proofs.
+///
+/// Client only connects to server of the same protocol version, so servers
+/// built from other revisions of `ere-server` are accepted as long as they
+/// speak the same protocol.
+pub const PROTOCOL_VERSION: u32 = 1;
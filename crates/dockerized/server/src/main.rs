@@ -1,10 +1,17 @@
-use anyhow::{Context, Error};
+use anyhow::{Context, Error, anyhow, bail};
 use clap::Parser;
-use ere_server::server::{router, zkVMServer};
-use ere_zkvm_interface::zkvm::{ProverResourceType, zkVM};
+use ere_server::{
+    client::ServerCapabilities,
+    server::{DEFAULT_JOB_CAPACITY, DEFAULT_JOB_RETENTION, router, zkVMServer},
+};
+use ere_zkvm_interface::zkvm::{
+    ProverResourceType, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
+};
 use std::{
+    fs,
     io::{self, Read},
     net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use tokio::{net::TcpListener, signal};
@@ -12,7 +19,7 @@ use tower_http::catch_panic::CatchPanicLayer;
 use tracing_subscriber::EnvFilter;
 use twirp::{
     Router,
-    axum::{self, routing::get},
+    axum::{self, http::header, routing::get},
     reqwest::StatusCode,
     server::not_found_handler,
 };
@@ -40,10 +47,24 @@ const _: () = {
 #[derive(Parser)]
 #[command(author, version)]
 struct Args {
+    /// Port to listen on, `0` to bind an ephemeral port.
     #[arg(long, default_value = "3000")]
     port: u16,
+    /// Writes the bound port to the file once listening, so the port bound
+    /// with `--port 0` can be discovered by the spawner.
+    #[arg(long)]
+    port_file: Option<PathBuf>,
+    /// Maximum number of queued and running jobs submitted by `SubmitProve`.
+    #[arg(long, default_value_t = DEFAULT_JOB_CAPACITY)]
+    job_capacity: usize,
+    /// Number of finished jobs to keep results for `GetResult`.
+    #[arg(long, default_value_t = DEFAULT_JOB_RETENTION)]
+    job_retention: usize,
+    /// Prints the capabilities of the zkVM as JSON and exits.
+    #[arg(long)]
+    capabilities: bool,
     #[command(subcommand)]
-    resource: ProverResourceType,
+    resource: Option<ProverResourceType>,
 }
 
 #[tokio::main]
@@ -54,23 +75,53 @@ async fn main() -> Result<(), Error> {
 
     let args = Args::parse();
 
+    if args.capabilities {
+        println!("{}", serde_json::to_string(&capabilities())?);
+        return Ok(());
+    }
+    let Some(resource) = args.resource else {
+        bail!("Prover resource type is required");
+    };
+
     // Read serialized program from stdin.
     let mut program = Vec::new();
     io::stdin().read_to_end(&mut program)?;
 
-    let zkvm = construct_zkvm(program, args.resource)?;
-    let server = Arc::new(zkVMServer::new(zkvm));
+    let zkvm = construct_zkvm(program, resource.clone())?;
+    let server = Arc::new(
+        zkVMServer::new(zkvm, resource.kind())
+            .with_job_queue(args.job_capacity, args.job_retention)
+            .with_program_loader(move |program| {
+                construct_zkvm(program, resource.clone())
+                    .map_err(|err| zkVMError::infrastructure(anyhow!("{err:#}")))
+            }),
+    );
     let app = Router::new()
-        .nest("/twirp", router(server))
+        .nest("/twirp", router(server.clone()))
         .route("/health", get(health))
+        .route(
+            "/metrics",
+            get(move || {
+                let server = server.clone();
+                async move {
+                    let content_type = [(header::CONTENT_TYPE, "text/plain; version=0.0.4")];
+                    (content_type, server.metrics())
+                }
+            }),
+        )
         .fallback(not_found_handler)
         .layer(CatchPanicLayer::new());
 
     let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), args.port);
     let tcp_listener = TcpListener::bind(addr).await?;
+    let addr = tcp_listener.local_addr()?;
 
     tracing::info!("Listening on {}", addr);
 
+    if let Some(port_file) = &args.port_file {
+        write_port_file(port_file, addr.port())?;
+    }
+
     axum::serve(tcp_listener, app)
         .with_graceful_shutdown(shutdown_signal())
         .await?;
@@ -80,6 +131,15 @@ async fn main() -> Result<(), Error> {
     Ok(())
 }
 
+/// Writes `port` to a temporary file then renames it to `path`, so the reader
+/// never sees a partially written port.
+fn write_port_file(path: &Path, port: u16) -> Result<(), Error> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, format!("{port}\n"))
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .with_context(|| format!("Failed to write port file {}", path.display()))
+}
+
 async fn health() -> StatusCode {
     StatusCode::OK
 }
@@ -108,7 +168,10 @@ async fn shutdown_signal() {
     }
 }
 
-fn construct_zkvm(program: Vec<u8>, resource: ProverResourceType) -> Result<impl zkVM, Error> {
+fn construct_zkvm(
+    program: Vec<u8>,
+    resource: ProverResourceType,
+) -> Result<impl zkVM + zkVMProgramDigest + zkVMCapabilities, Error> {
     let (program, _) = bincode::serde::decode_from_slice(&program, bincode::config::legacy())
         .with_context(|| "Failed to deserialize program")?;
 
@@ -144,3 +207,37 @@ fn construct_zkvm(program: Vec<u8>, resource: ProverResourceType) -> Result<impl
 
     zkvm.with_context(|| "Failed to instantiate zkVM")
 }
+
+fn capabilities() -> ServerCapabilities {
+    #[cfg(feature = "airbender")]
+    let capabilities = ServerCapabilities::of::<ere_airbender::zkvm::EreAirbender>();
+
+    #[cfg(feature = "jolt")]
+    let capabilities = ServerCapabilities::of::<ere_jolt::zkvm::EreJolt>();
+
+    #[cfg(feature = "miden")]
+    let capabilities = ServerCapabilities::of::<ere_miden::zkvm::EreMiden>();
+
+    #[cfg(feature = "nexus")]
+    let capabilities = ServerCapabilities::of::<ere_nexus::zkvm::EreNexus>();
+
+    #[cfg(feature = "openvm")]
+    let capabilities = ServerCapabilities::of::<ere_openvm::zkvm::EreOpenVM>();
+
+    #[cfg(feature = "pico")]
+    let capabilities = ServerCapabilities::of::<ere_pico::zkvm::ErePico>();
+
+    #[cfg(feature = "risc0")]
+    let capabilities = ServerCapabilities::of::<ere_risc0::zkvm::EreRisc0>();
+
+    #[cfg(feature = "sp1")]
+    let capabilities = ServerCapabilities::of::<ere_sp1::zkvm::EreSP1>();
+
+    #[cfg(feature = "ziren")]
+    let capabilities = ServerCapabilities::of::<ere_ziren::zkvm::EreZiren>();
+
+    #[cfg(feature = "zisk")]
+    let capabilities = ServerCapabilities::of::<ere_zisk::zkvm::EreZisk>();
+
+    capabilities
+}
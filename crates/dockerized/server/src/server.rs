@@ -1,31 +1,206 @@
 use crate::api::{
-    self, ExecuteOk, ExecuteRequest, ExecuteResponse, ProveOk, ProveRequest, ProveResponse,
-    VerifyOk, VerifyRequest, VerifyResponse, ZkvmService,
-    execute_response::Result as ExecuteResult, prove_response::Result as ProveResult,
+    self, CancelRequest, CancelResponse, ErrorCode, ExecuteOk, ExecuteRequest, ExecuteResponse,
+    GetJobRequest, GetJobResponse, GetResultRequest, GetResultResponse, InfoRequest, InfoResponse,
+    ListJobsRequest, ListJobsResponse, LoadProgramOk, LoadProgramRequest, LoadProgramResponse,
+    ProgramDigestOk, ProgramDigestRequest, ProgramDigestResponse, ProveOk, ProveRequest,
+    ProveResponse, ProveStatusRequest, ProveStatusResponse, ProvingStatus, SubmitProveResponse,
+    VerifyOk, VerifyRequest, VerifyResponse, ZkvmError, ZkvmService,
+    execute_response::Result as ExecuteResult, load_program_response::Result as LoadProgramResult,
+    program_digest_response::Result as ProgramDigestResult, prove_response::Result as ProveResult,
     verify_response::Result as VerifyResult,
 };
-use ere_zkvm_interface::zkvm::{Proof, ProofKind, zkVM};
+use crate::{CRATE_VERSION, PROTOCOL_VERSION};
+use ere_zkvm_interface::zkvm::{
+    CommonError, ExecuteOptions, Proof, ProofKind, ProveOptions, ProverResourceKind, zkVM,
+    zkVMCapabilities, zkVMError, zkVMProgramDigest,
+};
+use std::{
+    pin::pin,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+use tokio::{sync::Mutex, time::timeout};
 use twirp::{
-    Request, Response, TwirpErrorResponse, async_trait::async_trait, internal, invalid_argument,
+    Request, Response, TwirpErrorResponse, async_trait::async_trait, failed_precondition, internal,
+    invalid_argument, unavailable, unimplemented,
 };
 
+mod calls;
+mod job;
+mod metrics;
+mod proving;
+
+use calls::Calls;
+use job::{Jobs, job_id_of};
+use metrics::{Gauges, Method, Metrics};
+use proving::{Proving, Provings};
+
 pub use api::router;
+pub use job::{DEFAULT_JOB_CAPACITY, DEFAULT_JOB_RETENTION};
 
 /// zkVM server that handles the request by forwarding to the underlying
 /// [`zkVM`] implementation methods.
 #[allow(non_camel_case_types)]
 pub struct zkVMServer<T> {
-    zkvm: T,
+    /// zkVM with the current program, replaced by `LoadProgram`. `None` while
+    /// the program is being loaded or after loading failed.
+    zkvm: RwLock<Option<Arc<T>>>,
+    loader: Option<Arc<ProgramLoader<T>>>,
+    /// Held while loading program, so only one program is loaded at a time.
+    loading: Mutex<()>,
+    name: String,
+    sdk_version: String,
+    resource: ProverResourceKind,
+    provings: Arc<Provings>,
+    calls: Arc<Calls>,
+    jobs: Arc<Jobs>,
+    metrics: Arc<Metrics>,
 }
 
-impl<T: 'static + zkVM + Send + Sync> zkVMServer<T> {
-    pub fn new(zkvm: T) -> Self {
-        Self { zkvm }
+impl<T: 'static + zkVM + zkVMProgramDigest + zkVMCapabilities + Send + Sync> zkVMServer<T> {
+    /// Creates the server for the zkVM created with the `resource`, which is
+    /// reported by the `Info` request.
+    pub fn new(zkvm: T, resource: ProverResourceKind) -> Self {
+        Self {
+            metrics: Arc::new(Metrics::new(zkvm.name(), zkvm.sdk_version())),
+            name: zkvm.name().to_string(),
+            sdk_version: zkvm.sdk_version().to_string(),
+            zkvm: RwLock::new(Some(Arc::new(zkvm))),
+            loader: None,
+            loading: Mutex::new(()),
+            resource,
+            provings: Default::default(),
+            calls: Default::default(),
+            jobs: Arc::new(Jobs::new(DEFAULT_JOB_CAPACITY, DEFAULT_JOB_RETENTION)),
+        }
+    }
+
+    /// Sets the job queue to accept up to `capacity` queued and running jobs,
+    /// and keep results of the last `retention` finished jobs.
+    pub fn with_job_queue(mut self, capacity: usize, retention: usize) -> Self {
+        self.jobs = Arc::new(Jobs::new(capacity, retention));
+        self
+    }
+
+    /// Enables `LoadProgram` to replace the program, `loader` creates the zkVM
+    /// from the serialized program.
+    ///
+    /// The current zkVM is dropped before creating the new one, so resources
+    /// (e.g. ports and GPU memory) are not held by both of them. Therefore
+    /// `LoadProgram` is rejected while any request or job uses the current
+    /// zkVM, and the server has no program until loading succeeds.
+    pub fn with_program_loader(
+        mut self,
+        loader: impl Fn(Vec<u8>) -> Result<T, zkVMError> + Send + Sync + 'static,
+    ) -> Self {
+        self.loader = Some(Arc::new(loader));
+        self
+    }
+
+    /// Returns the zkVM with the current program, or `Unavailable` error if
+    /// no program is loaded.
+    fn zkvm(&self) -> twirp::Result<Arc<T>> {
+        self.zkvm
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| unavailable("no program is loaded"))
+    }
+
+    /// Returns the request metrics in Prometheus text format, labelled by the
+    /// zkVM name and SDK version.
+    pub fn metrics(&self) -> String {
+        let (queued_jobs, running_jobs) = self.jobs.num_pending();
+        self.metrics.render(Gauges {
+            inflight_provings: self.provings.len(),
+            queued_jobs,
+            running_jobs,
+        })
+    }
+
+    /// Returns status of the in-flight proving with the request id.
+    fn proving_of(&self, request_id: &str) -> Option<ProvingStatus> {
+        self.provings.status(Some(request_id)).into_iter().next()
     }
 }
 
+/// Creates the zkVM from a serialized program.
+type ProgramLoader<T> = dyn Fn(Vec<u8>) -> Result<T, zkVMError> + Send + Sync;
+
+/// Proves the request in blocking thread, so other requests (e.g. `Cancel`)
+/// can be handled meanwhile.
+///
+/// The `proving` should be registered by the caller before waiting for the
+/// permit to prove, so it could be cancelled while waiting, in which case it
+/// fails without calling the zkVM.
+///
+/// The peak memory is dropped from the report if other zkVM calls ran
+/// concurrently, since it's measured process-wide.
+async fn prove<T: 'static + zkVM + Send + Sync>(
+    zkvm: Arc<T>,
+    proving: &Proving,
+    calls: &Arc<Calls>,
+    metrics: &Metrics,
+    request: ProveRequest,
+) -> twirp::Result<ProveResult> {
+    let start = Instant::now();
+    let result = async move {
+        let input = request.input;
+        let proof_kind = ProofKind::from_repr(request.proof_kind as usize)
+            .ok_or_else(|| invalid_proof_kind_err(request.proof_kind))?;
+        if proving.cancellation_token().is_cancelled() {
+            return Ok(ProveResult::Err(
+                zkVMError::from(CommonError::Cancelled).into(),
+            ));
+        }
+        let options = ProveOptions {
+            cycle_limit: request.cycle_limit,
+            timeout: request.timeout_ms.map(Duration::from_millis),
+            cancellation_token: Some(proving.cancellation_token()),
+            progress: Some(proving.progress()),
+        };
+
+        let call = calls.start();
+        let result = tokio::task::spawn_blocking(move || {
+            let result = zkvm.prove_with_options(&input, proof_kind, &options);
+            (result, call.overlapped())
+        })
+        .await
+        .map_err(|err| internal(format!("proving task failed: {err}")))?;
+
+        Ok(match result {
+            (Ok((public_values, proof, mut report)), overlapped) => {
+                if overlapped {
+                    report.peak_memory = None;
+                }
+                ProveResult::Ok(ProveOk {
+                    public_values,
+                    proof: proof.as_bytes().to_vec(),
+                    report: bincode::serde::encode_to_vec(&report, bincode::config::legacy())
+                        .map_err(serialize_report_err)?,
+                })
+            }
+            (Err(err), _) => ProveResult::Err(err.into()),
+        })
+    }
+    .await;
+
+    match &result {
+        Ok(ProveResult::Ok(ok)) => {
+            metrics.observe_proof_size(ok.proof.len());
+            metrics.record(Method::Prove, start.elapsed(), None);
+        }
+        Ok(ProveResult::Err(err)) => metrics.record(Method::Prove, start.elapsed(), Some(err)),
+        Err(_) => metrics.record_rpc_error(Method::Prove, start.elapsed()),
+    }
+
+    result
+}
+
 #[async_trait]
-impl<T: 'static + zkVM + Send + Sync> ZkvmService for zkVMServer<T> {
+impl<T: 'static + zkVM + zkVMProgramDigest + zkVMCapabilities + Send + Sync> ZkvmService
+    for zkVMServer<T>
+{
     async fn execute(
         &self,
         request: Request<ExecuteRequest>,
@@ -33,15 +208,35 @@ impl<T: 'static + zkVM + Send + Sync> ZkvmService for zkVMServer<T> {
         let request = request.into_body();
 
         let input = request.input;
+        let options = ExecuteOptions {
+            cycle_limit: request.cycle_limit,
+        };
 
-        let result = match self.zkvm.execute(&input) {
-            Ok((public_values, report)) => ExecuteResult::Ok(ExecuteOk {
-                public_values,
-                report: bincode::serde::encode_to_vec(&report, bincode::config::legacy())
-                    .map_err(serialize_report_err)?,
-            }),
-            Err(err) => ExecuteResult::Err(err.to_string()),
+        // Executing might take a while, do it in blocking thread like proving.
+        let start = Instant::now();
+        let (zkvm, call) = (self.zkvm()?, self.calls.start());
+        let result = tokio::task::spawn_blocking(move || {
+            let _call = call;
+            zkvm.execute_with_options(&input, &options)
+        })
+        .await
+        .map_err(|err| internal(format!("executing task failed: {err}")))?;
+        let result = match result {
+            Ok((public_values, report)) => {
+                self.metrics.observe_cycles(report.total_num_cycles);
+                ExecuteResult::Ok(ExecuteOk {
+                    public_values,
+                    report: bincode::serde::encode_to_vec(&report, bincode::config::legacy())
+                        .map_err(serialize_report_err)?,
+                })
+            }
+            Err(err) => ExecuteResult::Err(err.into()),
         };
+        let err = match &result {
+            ExecuteResult::Ok(_) => None,
+            ExecuteResult::Err(err) => Some(err),
+        };
+        self.metrics.record(Method::Execute, start.elapsed(), err);
 
         Ok(Response::new(ExecuteResponse {
             result: Some(result),
@@ -52,22 +247,26 @@ impl<T: 'static + zkVM + Send + Sync> ZkvmService for zkVMServer<T> {
         &self,
         request: Request<ProveRequest>,
     ) -> twirp::Result<Response<ProveResponse>> {
-        let request = request.into_body();
+        // Interval to poll the cancellation while waiting for the permit.
+        const INTERVAL: Duration = Duration::from_millis(500);
 
-        let input = request.input;
-        let proof_kind = ProofKind::from_repr(request.proof_kind as usize)
-            .ok_or_else(|| invalid_proof_kind_err(request.proof_kind))?;
+        let request = request.into_body();
+        let zkvm = self.zkvm()?;
 
-        let result = match self.zkvm.prove(&input, proof_kind) {
-            Ok((public_values, proof, report)) => ProveResult::Ok(ProveOk {
-                public_values,
-                proof: proof.as_bytes().to_vec(),
-                report: bincode::serde::encode_to_vec(&report, bincode::config::legacy())
-                    .map_err(serialize_report_err)?,
-            }),
-            Err(err) => ProveResult::Err(err.to_string()),
+        // Take the same permit as jobs, so provings don't run concurrently.
+        let (_guard, proving) = self.provings.start(request.request_id.clone());
+        let mut permit = pin!(self.jobs.permit());
+        let _permit = loop {
+            if let Ok(permit) = timeout(INTERVAL, &mut permit).await {
+                break permit;
+            }
+            if proving.cancellation_token().is_cancelled() {
+                break None;
+            }
         };
 
+        let result = prove(zkvm, &proving, &self.calls, &self.metrics, request).await?;
+
         Ok(Response::new(ProveResponse {
             result: Some(result),
         }))
@@ -82,15 +281,244 @@ impl<T: 'static + zkVM + Send + Sync> ZkvmService for zkVMServer<T> {
         let proof_kind = ProofKind::from_repr(request.proof_kind as usize)
             .ok_or_else(|| invalid_proof_kind_err(request.proof_kind))?;
 
-        let result = match self.zkvm.verify(&Proof::new(proof_kind, request.proof)) {
+        let start = Instant::now();
+        let (zkvm, call) = (self.zkvm()?, self.calls.start());
+        let proof = Proof::new(proof_kind, request.proof);
+        let result = tokio::task::spawn_blocking(move || {
+            let _call = call;
+            zkvm.verify(&proof)
+        })
+        .await
+        .map_err(|err| internal(format!("verifying task failed: {err}")))?;
+        let result = match result {
             Ok(public_values) => VerifyResult::Ok(VerifyOk { public_values }),
-            Err(err) => VerifyResult::Err(err.to_string()),
+            Err(err) => VerifyResult::Err(err.into()),
+        };
+        let err = match &result {
+            VerifyResult::Ok(_) => None,
+            VerifyResult::Err(err) => Some(err),
         };
+        self.metrics.record(Method::Verify, start.elapsed(), err);
 
         Ok(Response::new(VerifyResponse {
             result: Some(result),
         }))
     }
+
+    async fn program_digest(
+        &self,
+        _: Request<ProgramDigestRequest>,
+    ) -> twirp::Result<Response<ProgramDigestResponse>> {
+        let result = match self.zkvm()?.program_digest_bytes() {
+            Ok(program_digest) => ProgramDigestResult::Ok(ProgramDigestOk { program_digest }),
+            Err(err) => ProgramDigestResult::Err(err.into()),
+        };
+
+        Ok(Response::new(ProgramDigestResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn info(&self, _: Request<InfoRequest>) -> twirp::Result<Response<InfoResponse>> {
+        // Available without program, so clients can still connect.
+        let program_digest = self
+            .zkvm()
+            .ok()
+            .and_then(|zkvm| zkvm.program_digest_bytes().ok());
+        Ok(Response::new(InfoResponse {
+            name: self.name.clone(),
+            sdk_version: self.sdk_version.clone(),
+            server_version: CRATE_VERSION.to_string(),
+            program_digest,
+            supported_proof_kinds: T::SUPPORTED_PROOF_KINDS
+                .iter()
+                .map(|proof_kind| *proof_kind as i32)
+                .collect(),
+            resource: api_resource_kind(self.resource) as i32,
+            supported_prover_resource_kinds: T::SUPPORTED_PROVER_RESOURCE_KINDS
+                .iter()
+                .map(|kind| api_resource_kind(*kind) as i32)
+                .collect(),
+            protocol_version: PROTOCOL_VERSION,
+        }))
+    }
+
+    async fn cancel(
+        &self,
+        request: Request<CancelRequest>,
+    ) -> twirp::Result<Response<CancelResponse>> {
+        let request = request.into_body();
+
+        // Jobs are cancelled first, a job starting in between has registered
+        // its proving, which is then cancelled.
+        let request_id = request.request_id.as_deref();
+        let num_cancelled = self.jobs.cancel(request_id);
+        let num_cancelled = num_cancelled + self.provings.cancel(request_id);
+
+        Ok(Response::new(CancelResponse { num_cancelled }))
+    }
+
+    async fn prove_status(
+        &self,
+        request: Request<ProveStatusRequest>,
+    ) -> twirp::Result<Response<ProveStatusResponse>> {
+        let request = request.into_body();
+
+        let provings = self.provings.status(request.request_id.as_deref());
+
+        Ok(Response::new(ProveStatusResponse { provings }))
+    }
+
+    async fn submit_prove(
+        &self,
+        request: Request<ProveRequest>,
+    ) -> twirp::Result<Response<SubmitProveResponse>> {
+        let mut request = request.into_body();
+
+        ProofKind::from_repr(request.proof_kind as usize)
+            .ok_or_else(|| invalid_proof_kind_err(request.proof_kind))?;
+        // Jobs are proved with the program when they are submitted.
+        let zkvm = self.zkvm()?;
+        let id = self.jobs.submit(&request)?;
+        let job_id = job_id_of(id);
+        request.request_id = Some(job_id.clone());
+
+        let (provings, calls, jobs, metrics) = (
+            self.provings.clone(),
+            self.calls.clone(),
+            self.jobs.clone(),
+            self.metrics.clone(),
+        );
+        tokio::spawn(async move {
+            let register = || provings.start(Some(job_id_of(id)));
+            if let Some((_permit, (_guard, proving))) = jobs.start(id, register).await {
+                let result = prove(zkvm, &proving, &calls, &metrics, request).await;
+                jobs.finish(id, result);
+            }
+        });
+
+        Ok(Response::new(SubmitProveResponse { job_id }))
+    }
+
+    async fn get_job(
+        &self,
+        request: Request<GetJobRequest>,
+    ) -> twirp::Result<Response<GetJobResponse>> {
+        let request = request.into_body();
+
+        let job = self.jobs.get(&request.job_id, |id| self.proving_of(id))?;
+
+        Ok(Response::new(GetJobResponse { job: Some(job) }))
+    }
+
+    async fn get_result(
+        &self,
+        request: Request<GetResultRequest>,
+    ) -> twirp::Result<Response<GetResultResponse>> {
+        let request = request.into_body();
+
+        let response = self.jobs.result(&request.job_id)?;
+
+        Ok(Response::new(response))
+    }
+
+    async fn list_jobs(
+        &self,
+        _: Request<ListJobsRequest>,
+    ) -> twirp::Result<Response<ListJobsResponse>> {
+        let jobs = self.jobs.list(|id| self.proving_of(id));
+
+        Ok(Response::new(ListJobsResponse { jobs }))
+    }
+
+    async fn load_program(
+        &self,
+        request: Request<LoadProgramRequest>,
+    ) -> twirp::Result<Response<LoadProgramResponse>> {
+        let loader = self
+            .loader
+            .clone()
+            .ok_or_else(|| unimplemented("loading program is not supported"))?;
+        let program = request.into_body().program;
+
+        let _loading = self
+            .loading
+            .try_lock()
+            .map_err(|_| failed_precondition("another program is being loaded"))?;
+
+        // Drop the current zkVM before creating the new one, which is only
+        // possible if no request or job holds it.
+        {
+            let mut zkvm = self.zkvm.write().unwrap();
+            if zkvm
+                .as_ref()
+                .is_some_and(|zkvm| Arc::strong_count(zkvm) > 1)
+            {
+                return Err(failed_precondition(
+                    "program can't be loaded while requests or jobs are pending",
+                ));
+            }
+            *zkvm = None;
+        }
+
+        // Setting up zkVM might take a while, do it in blocking thread.
+        let call = self.calls.start();
+        let result = tokio::task::spawn_blocking(move || {
+            let _call = call;
+            loader(program)
+        })
+        .await
+        .map_err(|err| internal(format!("loading program task failed: {err}")))?;
+
+        let result = match result {
+            Ok(zkvm) => {
+                let program_digest = zkvm.program_digest_bytes().ok();
+                *self.zkvm.write().unwrap() = Some(Arc::new(zkvm));
+                LoadProgramResult::Ok(LoadProgramOk { program_digest })
+            }
+            Err(err) => LoadProgramResult::Err(err.into()),
+        };
+
+        Ok(Response::new(LoadProgramResponse {
+            result: Some(result),
+        }))
+    }
+}
+
+impl From<zkVMError> for ZkvmError {
+    fn from(err: zkVMError) -> Self {
+        let (exit_code, cycle_limit) = match &err {
+            zkVMError::GuestPanic { exit_code, .. } => (*exit_code, None),
+            zkVMError::CycleLimitExceeded { cycle_limit } => (None, Some(*cycle_limit)),
+            _ => (None, None),
+        };
+        let (code, message) = match err {
+            zkVMError::GuestPanic { message, .. } => (ErrorCode::GuestPanic, message),
+            zkVMError::InvalidInput(err) => (ErrorCode::InvalidInput, err.to_string()),
+            zkVMError::CycleLimitExceeded { .. } => {
+                (ErrorCode::CycleLimitExceeded, err.to_string())
+            }
+            zkVMError::InvalidProof(err) => (ErrorCode::InvalidProof, err.to_string()),
+            zkVMError::Unsupported(err) => (ErrorCode::Unsupported, err.to_string()),
+            zkVMError::Cancelled(err) => (ErrorCode::Cancelled, err.to_string()),
+            zkVMError::Infrastructure(err) => (ErrorCode::Infrastructure, err.to_string()),
+        };
+        Self {
+            code: code as i32,
+            message,
+            exit_code,
+            cycle_limit,
+        }
+    }
+}
+
+fn api_resource_kind(resource: ProverResourceKind) -> api::ProverResourceKind {
+    match resource {
+        ProverResourceKind::Cpu => api::ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu => api::ProverResourceKind::Gpu,
+        ProverResourceKind::Network => api::ProverResourceKind::Network,
+        ProverResourceKind::Mock => api::ProverResourceKind::Mock,
+    }
 }
 
 fn invalid_proof_kind_err(proof_kind: i32) -> TwirpErrorResponse {
@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+
+/// zkVM calls running concurrently, so a proving can tell whether the
+/// process-wide peak memory it measures is affected by other calls.
+#[derive(Default)]
+pub(super) struct Calls {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Number of running calls.
+    running: usize,
+    /// Number of calls started so far.
+    started: u64,
+}
+
+impl Calls {
+    /// Registers a running call, which is unregistered when the returned
+    /// [`CallGuard`] is dropped.
+    pub(super) fn start(self: &Arc<Self>) -> CallGuard {
+        let mut inner = self.inner.lock().unwrap();
+        inner.running += 1;
+        inner.started += 1;
+        CallGuard {
+            calls: self.clone(),
+            concurrent_at_start: inner.running > 1,
+            started_at_start: inner.started,
+        }
+    }
+}
+
+/// Unregisters the running call on drop.
+pub(super) struct CallGuard {
+    calls: Arc<Calls>,
+    concurrent_at_start: bool,
+    started_at_start: u64,
+}
+
+impl CallGuard {
+    /// Returns whether any other call has run concurrently since the start.
+    pub(super) fn overlapped(&self) -> bool {
+        self.concurrent_at_start
+            || self.calls.inner.lock().unwrap().started != self.started_at_start
+    }
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        self.calls.inner.lock().unwrap().running -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Calls;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_overlapped() {
+        let calls = Arc::new(Calls::default());
+
+        let first = calls.start();
+        assert!(!first.overlapped());
+        drop(first);
+
+        let first = calls.start();
+        let second = calls.start();
+        assert!(first.overlapped());
+        assert!(second.overlapped());
+        drop(second);
+        assert!(first.overlapped());
+        drop(first);
+
+        assert!(!calls.start().overlapped());
+    }
+}
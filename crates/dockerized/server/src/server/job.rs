@@ -0,0 +1,365 @@
+use crate::api::{
+    self, GetResultResponse, JobState, ProveRequest, ProvingStatus, get_result_response,
+    prove_response::Result as ProveResult,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use twirp::{TwirpErrorResponse, failed_precondition, not_found, resource_exhausted};
+
+/// Default maximum number of queued and running jobs.
+pub const DEFAULT_JOB_CAPACITY: usize = 16;
+
+/// Default number of finished jobs kept for `GetResult`.
+pub const DEFAULT_JOB_RETENTION: usize = 64;
+
+/// Bounded in-memory queue of jobs submitted by `SubmitProve`.
+///
+/// Jobs are proved one at a time in submission order, and the finished jobs
+/// are kept until there are more than `retention` of them, then the oldest
+/// ones are dropped.
+pub(super) struct Jobs {
+    capacity: usize,
+    retention: usize,
+    /// Jobs keyed by an increasing id, so they are iterated in submission
+    /// order.
+    jobs: Mutex<BTreeMap<u64, Job>>,
+    next_id: AtomicU64,
+    /// Single permit to prove, acquired by jobs in submission order.
+    permit: Semaphore,
+}
+
+struct Job {
+    state: JobState,
+    submitted: Instant,
+    /// Time between submission and finish, set when finished.
+    elapsed: Option<Duration>,
+    proof_kind: i32,
+    /// Set when finished.
+    result: Option<twirp::Result<ProveResult>>,
+}
+
+impl Jobs {
+    pub(super) fn new(capacity: usize, retention: usize) -> Self {
+        Self {
+            capacity,
+            retention,
+            jobs: Mutex::new(BTreeMap::new()),
+            next_id: AtomicU64::new(0),
+            permit: Semaphore::new(1),
+        }
+    }
+
+    /// Queues a new job, or returns `resource_exhausted` error if there are
+    /// already `capacity` jobs queued or running.
+    pub(super) fn submit(&self, request: &ProveRequest) -> twirp::Result<u64> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let num_pending = jobs.values().filter(|job| job.result.is_none()).count();
+        if num_pending >= self.capacity {
+            return Err(resource_exhausted(format!(
+                "job queue is full ({} jobs queued or running)",
+                self.capacity
+            )));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        jobs.insert(
+            id,
+            Job {
+                state: JobState::Queued,
+                submitted: Instant::now(),
+                elapsed: None,
+                proof_kind: request.proof_kind,
+                result: None,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Waits for the single permit to prove, which is shared by jobs and
+    /// `Prove` requests and acquired in arrival order.
+    pub(super) async fn permit(&self) -> Option<SemaphorePermit<'_>> {
+        self.permit.acquire().await.ok()
+    }
+
+    /// Waits for the previous jobs to finish, then marks the job as running
+    /// and calls `register` in the same critical section, so the proving
+    /// registered by it can be cancelled as soon as the job leaves the queue.
+    ///
+    /// Returns `None` if the job is no longer queued (e.g. cancelled), the
+    /// permit should be held until the job is finished otherwise.
+    pub(super) async fn start<R>(
+        &self,
+        id: u64,
+        register: impl FnOnce() -> R,
+    ) -> Option<(SemaphorePermit<'_>, R)> {
+        let permit = self.permit().await?;
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .get_mut(&id)
+            .filter(|job| job.state == JobState::Queued)?;
+        job.state = JobState::Running;
+        Some((permit, register()))
+    }
+
+    /// Records result of the job, and drops the oldest finished jobs beyond
+    /// `retention`.
+    pub(super) fn finish(&self, id: u64, result: twirp::Result<ProveResult>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(&id) {
+            job.finish(result);
+        }
+        self.drop_expired(&mut jobs);
+    }
+
+    /// Fails the queued jobs with the job id, or all queued jobs if it's
+    /// `None`, as cancelled. Returns the number of jobs cancelled.
+    ///
+    /// It's done in a single critical section, so jobs that have started
+    /// running are skipped. They are cancelled by their in-flight proving
+    /// instead, which should be done after this, since a job could start
+    /// running right before it.
+    pub(super) fn cancel(&self, job_id: Option<&str>) -> u32 {
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut cancelled = 0;
+        for (id, job) in jobs.iter_mut() {
+            if job.state != JobState::Queued
+                || job_id.is_some_and(|target| target != job_id_of(*id))
+            {
+                continue;
+            }
+            let err = api::ZkvmError {
+                code: api::ErrorCode::Cancelled as i32,
+                message: "Job is cancelled before running".to_string(),
+                exit_code: None,
+                cycle_limit: None,
+            };
+            job.finish(Ok(ProveResult::Err(err)));
+            cancelled += 1;
+        }
+        self.drop_expired(&mut jobs);
+        cancelled
+    }
+
+    /// Drops the oldest finished jobs beyond `retention`.
+    fn drop_expired(&self, jobs: &mut BTreeMap<u64, Job>) {
+        let finished = jobs
+            .iter()
+            .filter(|(_, job)| job.result.is_some())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(self.retention))
+        {
+            jobs.remove(id);
+        }
+    }
+
+    /// Returns status of the job, the progress is queried by `proving_of`
+    /// with the job id if the job is running.
+    pub(super) fn get(
+        &self,
+        job_id: &str,
+        proving_of: impl Fn(&str) -> Option<ProvingStatus>,
+    ) -> twirp::Result<api::Job> {
+        let id = parse_job_id(job_id)?;
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs.get(&id).ok_or_else(|| job_not_found_err(job_id))?;
+        Ok(job.status(id, &proving_of))
+    }
+
+    /// Returns the number of queued and running jobs.
+    pub(super) fn num_pending(&self) -> (usize, usize) {
+        let jobs = self.jobs.lock().unwrap();
+        let count = |state| jobs.values().filter(|job| job.state == state).count();
+        (count(JobState::Queued), count(JobState::Running))
+    }
+
+    /// Returns status of all jobs in submission order.
+    pub(super) fn list(&self, proving_of: impl Fn(&str) -> Option<ProvingStatus>) -> Vec<api::Job> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, job)| job.status(*id, &proving_of))
+            .collect()
+    }
+
+    /// Returns result of the job, or `failed_precondition` error if the job
+    /// is not finished yet.
+    pub(super) fn result(&self, job_id: &str) -> twirp::Result<GetResultResponse> {
+        let id = parse_job_id(job_id)?;
+        let jobs = self.jobs.lock().unwrap();
+        let job = jobs.get(&id).ok_or_else(|| job_not_found_err(job_id))?;
+        let result = match job.result.clone() {
+            Some(result) => result?,
+            None => {
+                return Err(failed_precondition(format!(
+                    "job {job_id} is not finished yet"
+                )));
+            }
+        };
+        Ok(GetResultResponse {
+            result: Some(match result {
+                ProveResult::Ok(ok) => get_result_response::Result::Ok(ok),
+                ProveResult::Err(err) => get_result_response::Result::Err(err),
+            }),
+            proof_kind: job.proof_kind,
+        })
+    }
+}
+
+impl Job {
+    fn finish(&mut self, result: twirp::Result<ProveResult>) {
+        self.state = match &result {
+            Ok(ProveResult::Ok(_)) => JobState::Succeeded,
+            Ok(ProveResult::Err(_)) | Err(_) => JobState::Failed,
+        };
+        self.elapsed = Some(self.submitted.elapsed());
+        self.result = Some(result);
+    }
+
+    fn status(&self, id: u64, proving_of: &impl Fn(&str) -> Option<ProvingStatus>) -> api::Job {
+        let job_id = job_id_of(id);
+        let proving = (self.state == JobState::Running)
+            .then(|| proving_of(&job_id))
+            .flatten()
+            .unwrap_or_default();
+        let elapsed = self.elapsed.unwrap_or_else(|| self.submitted.elapsed());
+        api::Job {
+            job_id,
+            state: self.state as i32,
+            phase: proving.phase,
+            elapsed_ms: elapsed.as_millis() as u64,
+            segments: proving.segments,
+        }
+    }
+}
+
+/// Returns the job id exposed to clients, which is also used as the request id
+/// of its proving.
+pub(super) fn job_id_of(id: u64) -> String {
+    format!("job-{id}")
+}
+
+fn parse_job_id(job_id: &str) -> twirp::Result<u64> {
+    job_id
+        .strip_prefix("job-")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| job_not_found_err(job_id))
+}
+
+fn job_not_found_err(job_id: &str) -> TwirpErrorResponse {
+    not_found(format!("job {job_id} not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Jobs, job_id_of};
+    use crate::api::{
+        ErrorCode, JobState, ProveOk, ProveRequest, ZkvmError, get_result_response,
+        prove_response::Result as ProveResult,
+    };
+    use twirp::TwirpErrorCode;
+
+    fn ok() -> ProveResult {
+        ProveResult::Ok(ProveOk::default())
+    }
+
+    fn state(jobs: &Jobs, id: u64) -> JobState {
+        let job = jobs.get(&job_id_of(id), |_| None).unwrap();
+        JobState::try_from(job.state).unwrap()
+    }
+
+    #[test]
+    fn test_capacity() {
+        let jobs = Jobs::new(2, 8);
+        let request = ProveRequest::default();
+
+        let first = jobs.submit(&request).unwrap();
+        jobs.submit(&request).unwrap();
+        let err = jobs.submit(&request).unwrap_err();
+        assert_eq!(err.code, TwirpErrorCode::ResourceExhausted);
+
+        // Finished jobs don't count.
+        jobs.finish(first, Ok(ok()));
+        jobs.submit(&request).unwrap();
+        assert_eq!(jobs.num_pending(), (2, 0));
+    }
+
+    #[test]
+    fn test_retention() {
+        let jobs = Jobs::new(8, 2);
+        let request = ProveRequest::default();
+
+        let ids = (0..3)
+            .map(|_| jobs.submit(&request).unwrap())
+            .collect::<Vec<_>>();
+        for id in &ids {
+            jobs.finish(*id, Ok(ok()));
+        }
+
+        // The oldest finished job is dropped.
+        let err = jobs.result(&job_id_of(ids[0])).unwrap_err();
+        assert_eq!(err.code, TwirpErrorCode::NotFound);
+        for id in &ids[1..] {
+            let response = jobs.result(&job_id_of(*id)).unwrap();
+            assert!(matches!(
+                response.result,
+                Some(get_result_response::Result::Ok(_))
+            ));
+        }
+        assert_eq!(jobs.list(|_| None).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued() {
+        let jobs = Jobs::new(8, 8);
+        let request = ProveRequest::default();
+
+        let running = jobs.submit(&request).unwrap();
+        let queued = jobs.submit(&request).unwrap();
+        let permit = jobs.start(running, || ()).await.unwrap();
+
+        // Only the queued job is cancelled, the running one is left to its
+        // proving.
+        assert_eq!(jobs.cancel(None), 1);
+        assert_eq!(state(&jobs, running), JobState::Running);
+        assert_eq!(state(&jobs, queued), JobState::Failed);
+        let response = jobs.result(&job_id_of(queued)).unwrap();
+        assert!(matches!(
+            response.result,
+            Some(get_result_response::Result::Err(ZkvmError { code, .. }))
+                if code == ErrorCode::Cancelled as i32
+        ));
+
+        // Cancelled job doesn't start, and doesn't register its proving.
+        drop(permit);
+        assert!(
+            jobs.start(queued, || unreachable!("cancelled job is registered"))
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running() {
+        let jobs = Jobs::new(8, 8);
+        let request = ProveRequest::default();
+
+        let id = jobs.submit(&request).unwrap();
+        let (_permit, registered) = jobs.start(id, || "proving").await.unwrap();
+        assert_eq!(registered, "proving");
+
+        assert_eq!(jobs.cancel(Some(&job_id_of(id))), 0);
+        assert_eq!(state(&jobs, id), JobState::Running);
+        let err = jobs.result(&job_id_of(id)).unwrap_err();
+        assert_eq!(err.code, TwirpErrorCode::FailedPrecondition);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::api::{ErrorCode, ZkvmError};
+use std::{collections::BTreeMap, fmt::Write, sync::Mutex, time::Duration};
+
+/// Buckets of request durations in seconds, from 10ms to 3 hours.
+const DURATION_BUCKETS: &[f64] = &[
+    0.01, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 600.0, 1800.0, 3600.0, 10800.0,
+];
+
+/// Buckets of execution cycles.
+const CYCLES_BUCKETS: &[f64] = &[1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11];
+
+/// Buckets of proof sizes in bytes.
+const PROOF_SIZE_BUCKETS: &[f64] = &[1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8];
+
+/// Method of the request recorded by [`Metrics`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Method {
+    Execute,
+    Prove,
+    Verify,
+}
+
+impl Method {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Execute => "execute",
+            Self::Prove => "prove",
+            Self::Verify => "verify",
+        }
+    }
+}
+
+/// Gauges sampled when the metrics are rendered.
+pub(super) struct Gauges {
+    pub(super) inflight_provings: usize,
+    pub(super) queued_jobs: usize,
+    pub(super) running_jobs: usize,
+}
+
+/// Request metrics of the server, rendered in Prometheus text format for the
+/// `/metrics` endpoint.
+pub(super) struct Metrics {
+    /// Labels of the zkVM attached to all metrics.
+    labels: String,
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    /// Number of requests keyed by method and result, the result is either
+    /// `ok`, the error class returned by the zkVM, or `rpc_error`.
+    requests: BTreeMap<(Method, &'static str), u64>,
+    durations: BTreeMap<Method, Histogram>,
+    cycles: Histogram,
+    proof_size: Histogram,
+}
+
+impl Metrics {
+    pub(super) fn new(zkvm_name: &str, sdk_version: &str) -> Self {
+        Self {
+            labels: format!(
+                "zkvm=\"{}\",sdk_version=\"{}\"",
+                escape(zkvm_name),
+                escape(sdk_version)
+            ),
+            inner: Mutex::new(Inner {
+                requests: BTreeMap::new(),
+                durations: BTreeMap::new(),
+                cycles: Histogram::new(CYCLES_BUCKETS),
+                proof_size: Histogram::new(PROOF_SIZE_BUCKETS),
+            }),
+        }
+    }
+
+    /// Records a finished request, with the error returned by the zkVM if any.
+    pub(super) fn record(&self, method: Method, elapsed: Duration, err: Option<&ZkvmError>) {
+        self.record_result(method, elapsed, err.map_or("ok", error_class))
+    }
+
+    /// Records a request that failed before or after calling the zkVM, e.g.
+    /// with invalid argument.
+    pub(super) fn record_rpc_error(&self, method: Method, elapsed: Duration) {
+        self.record_result(method, elapsed, "rpc_error")
+    }
+
+    fn record_result(&self, method: Method, elapsed: Duration, result: &'static str) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner.requests.entry((method, result)).or_default() += 1;
+        inner
+            .durations
+            .entry(method)
+            .or_insert_with(|| Histogram::new(DURATION_BUCKETS))
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub(super) fn observe_cycles(&self, cycles: u64) {
+        self.inner.lock().unwrap().cycles.observe(cycles as f64);
+    }
+
+    pub(super) fn observe_proof_size(&self, proof_size: usize) {
+        self.inner
+            .lock()
+            .unwrap()
+            .proof_size
+            .observe(proof_size as f64);
+    }
+
+    /// Renders the metrics in Prometheus text exposition format.
+    pub(super) fn render(&self, gauges: Gauges) -> String {
+        let inner = self.inner.lock().unwrap();
+        let labels = &self.labels;
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "ere_requests_total",
+            "counter",
+            "Number of finished requests by method and result.",
+        );
+        for ((method, result), count) in &inner.requests {
+            let method = method.as_str();
+            writeln!(
+                out,
+                "ere_requests_total{{{labels},method=\"{method}\",result=\"{result}\"}} {count}"
+            )
+            .unwrap();
+        }
+
+        header(
+            &mut out,
+            "ere_request_duration_seconds",
+            "histogram",
+            "Duration of finished requests by method.",
+        );
+        for (method, histogram) in &inner.durations {
+            let labels = format!("{labels},method=\"{}\"", method.as_str());
+            histogram.render(&mut out, "ere_request_duration_seconds", &labels);
+        }
+
+        header(
+            &mut out,
+            "ere_execution_cycles",
+            "histogram",
+            "Total cycles of successful executions.",
+        );
+        inner
+            .cycles
+            .render(&mut out, "ere_execution_cycles", labels);
+
+        header(
+            &mut out,
+            "ere_proof_size_bytes",
+            "histogram",
+            "Size of proofs created by successful provings.",
+        );
+        inner
+            .proof_size
+            .render(&mut out, "ere_proof_size_bytes", labels);
+
+        header(
+            &mut out,
+            "ere_inflight_provings",
+            "gauge",
+            "Number of in-flight provings, including running jobs.",
+        );
+        writeln!(
+            out,
+            "ere_inflight_provings{{{labels}}} {}",
+            gauges.inflight_provings
+        )
+        .unwrap();
+
+        header(
+            &mut out,
+            "ere_jobs",
+            "gauge",
+            "Number of pending jobs by state.",
+        );
+        for (state, count) in [
+            ("queued", gauges.queued_jobs),
+            ("running", gauges.running_jobs),
+        ] {
+            writeln!(out, "ere_jobs{{{labels},state=\"{state}\"}} {count}").unwrap();
+        }
+
+        out
+    }
+}
+
+/// Cumulative histogram with fixed buckets.
+struct Histogram {
+    /// Upper bounds of the buckets, in ascending order.
+    buckets: &'static [f64],
+    /// Number of observations less than or equal to each bucket bound.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &'static [f64]) -> Self {
+        Self {
+            buckets,
+            counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.buckets.iter().zip(&mut self.counts) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bound, count) in self.buckets.iter().zip(&self.counts) {
+            writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}").unwrap();
+        }
+        let count = self.count;
+        writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {count}").unwrap();
+        writeln!(out, "{name}_sum{{{labels}}} {}", self.sum).unwrap();
+        writeln!(out, "{name}_count{{{labels}}} {count}").unwrap();
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(out, "# HELP {name} {help}").unwrap();
+    writeln!(out, "# TYPE {name} {kind}").unwrap();
+}
+
+/// Returns class of the error as metric label.
+fn error_class(err: &ZkvmError) -> &'static str {
+    match ErrorCode::try_from(err.code).unwrap_or(ErrorCode::Infrastructure) {
+        ErrorCode::Infrastructure => "infrastructure",
+        ErrorCode::GuestPanic => "guest_panic",
+        ErrorCode::InvalidInput => "invalid_input",
+        ErrorCode::CycleLimitExceeded => "cycle_limit_exceeded",
+        ErrorCode::InvalidProof => "invalid_proof",
+        ErrorCode::Unsupported => "unsupported",
+        ErrorCode::Cancelled => "cancelled",
+    }
+}
+
+/// Escapes label value as required by Prometheus text format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gauges, Histogram, Method, Metrics};
+    use crate::api::{ErrorCode, ZkvmError};
+    use std::time::Duration;
+
+    const GAUGES: Gauges = Gauges {
+        inflight_provings: 1,
+        queued_jobs: 2,
+        running_jobs: 3,
+    };
+
+    #[test]
+    fn test_escape_labels() {
+        let metrics = Metrics::new("sp1", "v5 \"rc\"\\\n");
+        let out = metrics.render(GAUGES);
+        assert!(out.contains(
+            "ere_inflight_provings{zkvm=\"sp1\",sdk_version=\"v5 \\\"rc\\\"\\\\\\n\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut histogram = Histogram::new(&[1.0, 10.0]);
+        for value in [0.5, 1.0, 5.0, 20.0] {
+            histogram.observe(value);
+        }
+
+        let mut out = String::new();
+        histogram.render(&mut out, "m", "l=\"v\"");
+        assert_eq!(
+            out,
+            concat!(
+                "m_bucket{l=\"v\",le=\"1\"} 2\n",
+                "m_bucket{l=\"v\",le=\"10\"} 3\n",
+                "m_bucket{l=\"v\",le=\"+Inf\"} 4\n",
+                "m_sum{l=\"v\"} 26.5\n",
+                "m_count{l=\"v\"} 4\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new("sp1", "v5");
+        let cancelled = ZkvmError {
+            code: ErrorCode::Cancelled as i32,
+            ..Default::default()
+        };
+        metrics.record(Method::Prove, Duration::from_millis(200), None);
+        metrics.record(Method::Prove, Duration::from_secs(2), Some(&cancelled));
+        metrics.record_rpc_error(Method::Verify, Duration::ZERO);
+        metrics.observe_cycles(1000);
+        metrics.observe_proof_size(1000);
+
+        let out = metrics.render(GAUGES);
+        let labels = "zkvm=\"sp1\",sdk_version=\"v5\"";
+        for line in [
+            format!("ere_requests_total{{{labels},method=\"prove\",result=\"ok\"}} 1"),
+            format!("ere_requests_total{{{labels},method=\"prove\",result=\"cancelled\"}} 1"),
+            format!("ere_requests_total{{{labels},method=\"verify\",result=\"rpc_error\"}} 1"),
+            format!(
+                "ere_request_duration_seconds_bucket{{{labels},method=\"prove\",le=\"0.1\"}} 0"
+            ),
+            format!(
+                "ere_request_duration_seconds_bucket{{{labels},method=\"prove\",le=\"0.5\"}} 1"
+            ),
+            format!("ere_request_duration_seconds_bucket{{{labels},method=\"prove\",le=\"5\"}} 2"),
+            format!("ere_request_duration_seconds_sum{{{labels},method=\"prove\"}} 2.2"),
+            format!("ere_request_duration_seconds_count{{{labels},method=\"verify\"}} 1"),
+            format!("ere_execution_cycles_bucket{{{labels},le=\"100000\"}} 1"),
+            format!("ere_proof_size_bytes_bucket{{{labels},le=\"100\"}} 0"),
+            format!("ere_proof_size_bytes_bucket{{{labels},le=\"+Inf\"}} 1"),
+            format!("ere_jobs{{{labels},state=\"queued\"}} 2"),
+            format!("ere_jobs{{{labels},state=\"running\"}} 3"),
+        ] {
+            assert!(
+                out.lines().any(|l| l == line),
+                "missing {line:?} in:\n{out}"
+            );
+        }
+        // Each metric has a single header.
+        assert_eq!(out.matches("# TYPE ere_requests_total counter").count(), 1);
+        assert!(!out.contains("method=\"execute\""));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::api::{self, ProvingStatus};
+use ere_zkvm_interface::zkvm::{CancellationToken, ProgressCallback, ProveProgress};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+/// In-flight provings, for `Cancel` and `ProveStatus` requests.
+#[derive(Default)]
+pub(super) struct Provings {
+    /// Provings keyed by an increasing id.
+    provings: Mutex<BTreeMap<u64, Proving>>,
+    next_id: AtomicU64,
+}
+
+impl Provings {
+    /// Registers an in-flight proving, which is unregistered when the
+    /// returned [`ProvingGuard`] is dropped.
+    pub(super) fn start(self: &Arc<Self>, request_id: Option<String>) -> (ProvingGuard, Proving) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let proving = Proving {
+            request_id,
+            token: CancellationToken::new(),
+            progress: Default::default(),
+            start: Instant::now(),
+        };
+        self.provings.lock().unwrap().insert(id, proving.clone());
+        let guard = ProvingGuard {
+            provings: self.clone(),
+            id,
+        };
+        (guard, proving)
+    }
+
+    /// Returns the number of in-flight provings.
+    pub(super) fn len(&self) -> usize {
+        self.provings.lock().unwrap().len()
+    }
+
+    /// Cancels the provings with the request id, or all if it's `None`.
+    /// Returns the number of provings cancelled.
+    pub(super) fn cancel(&self, request_id: Option<&str>) -> u32 {
+        let provings = self.provings.lock().unwrap();
+        let mut num_cancelled = 0;
+        for proving in provings.values() {
+            if proving.matches(request_id) {
+                proving.token.cancel();
+                num_cancelled += 1;
+            }
+        }
+        num_cancelled
+    }
+
+    /// Returns status of the provings with the request id, or all if it's
+    /// `None`.
+    pub(super) fn status(&self, request_id: Option<&str>) -> Vec<ProvingStatus> {
+        self.provings
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|proving| proving.matches(request_id))
+            .map(Proving::status)
+            .collect()
+    }
+}
+
+/// In-flight proving.
+#[derive(Clone)]
+pub(super) struct Proving {
+    request_id: Option<String>,
+    token: CancellationToken,
+    progress: Arc<Mutex<Option<ProveProgress>>>,
+    start: Instant,
+}
+
+impl Proving {
+    pub(super) fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Returns [`ProgressCallback`] that records the progress for
+    /// `ProveStatus`.
+    pub(super) fn progress(&self) -> ProgressCallback {
+        let progress = self.progress.clone();
+        ProgressCallback::new(move |current| *progress.lock().unwrap() = Some(current))
+    }
+
+    /// Returns whether the proving matches the request id, all provings match
+    /// if it's `None`.
+    fn matches(&self, request_id: Option<&str>) -> bool {
+        request_id.is_none() || self.request_id.as_deref() == request_id
+    }
+
+    fn status(&self) -> ProvingStatus {
+        let progress = *self.progress.lock().unwrap();
+        ProvingStatus {
+            request_id: self.request_id.clone(),
+            phase: progress.map(|progress| progress.phase as i32),
+            elapsed_ms: self.start.elapsed().as_millis() as u64,
+            segments: progress
+                .and_then(|progress| progress.segments)
+                .map(|segments| api::SegmentProgress {
+                    proved: segments.proved,
+                    total: segments.total,
+                }),
+        }
+    }
+}
+
+/// Unregisters the in-flight proving on drop. If the request is dropped (e.g.
+/// client disconnected) before the proving finishes, the proving is cancelled
+/// as well.
+pub(super) struct ProvingGuard {
+    provings: Arc<Provings>,
+    id: u64,
+}
+
+impl Drop for ProvingGuard {
+    fn drop(&mut self) {
+        if let Some(proving) = self.provings.provings.lock().unwrap().remove(&self.id) {
+            proving.token.cancel();
+        }
+    }
+}
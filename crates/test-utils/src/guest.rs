@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 
 pub use sha2::{Digest, Sha256};
 
@@ -9,4 +9,41 @@ pub trait Platform {
 
     /// Write the whole output at once to host.
     fn write_output(output: &[u8]);
+
+    /// Returns the current cycle count, `None` if it's not exposed to guest.
+    fn cycle_count() -> Option<u64> {
+        None
+    }
+
+    /// Print a line to host stdout, nothing is printed by default.
+    fn print(_line: &str) {}
+
+    /// Starts a region for cycle tracking.
+    ///
+    /// The marker follows `ere_zkvm_interface::zkvm::REGION_START_MARKER`.
+    fn region_start(name: &str) {
+        Self::print(&region_marker(
+            "cycle-tracker-report-start",
+            name,
+            Self::cycle_count(),
+        ));
+    }
+
+    /// Ends a region for cycle tracking.
+    ///
+    /// The marker follows `ere_zkvm_interface::zkvm::REGION_END_MARKER`.
+    fn region_end(name: &str) {
+        Self::print(&region_marker(
+            "cycle-tracker-report-end",
+            name,
+            Self::cycle_count(),
+        ));
+    }
+}
+
+fn region_marker(prefix: &str, name: &str, cycle_count: Option<u64>) -> String {
+    match cycle_count {
+        Some(cycles) => format!("{prefix}: {name} @ {cycles}"),
+        None => format!("{prefix}: {name}"),
+    }
 }
@@ -1,6 +1,8 @@
 use crate::program::{Program, ProgramInput};
 use ere_io_serde::IoSerde;
-use ere_zkvm_interface::zkvm::{ProofKind, PublicValues, zkVM};
+use ere_zkvm_interface::zkvm::{
+    ExecuteOptions, ProofKind, ProveOptions, PublicValues, zkVM, zkVMError, zkVMVerifier,
+};
 use sha2::Digest;
 use std::{marker::PhantomData, path::PathBuf};
 
@@ -25,12 +27,84 @@ pub fn run_zkvm_execute(zkvm: &impl zkVM, test_case: &impl TestCase) -> PublicVa
     public_values
 }
 
+/// Executes and asserts the `compute` region marked by [`Program::run`] is
+/// tracked in [`ProgramExecutionReport::region_cycles`].
+///
+/// [`ProgramExecutionReport::region_cycles`]: ere_zkvm_interface::zkvm::ProgramExecutionReport::region_cycles
+pub fn run_zkvm_region_cycles(zkvm: &impl zkVM, test_case: &impl TestCase) {
+    let (_, report) = zkvm
+        .execute(&test_case.serialized_input())
+        .expect("execute should not fail with valid input");
+
+    assert!(report.region_cycles["compute"] > 0);
+}
+
 pub fn run_zkvm_prove(zkvm: &impl zkVM, test_case: &impl TestCase) -> PublicValues {
+    run_zkvm_prove_with_proof_kind(zkvm, test_case, ProofKind::default())
+}
+
+pub fn run_zkvm_prove_with_proof_kind(
+    zkvm: &impl zkVM,
+    test_case: &impl TestCase,
+    proof_kind: ProofKind,
+) -> PublicValues {
+    let (prover_public_values, proof, report) = zkvm
+        .prove(&test_case.serialized_input(), proof_kind)
+        .expect("prove should not fail with valid input");
+
+    assert_eq!(report.proof_size, proof.as_bytes().len() as u64);
+
+    let verifier_public_values = zkvm
+        .verify(&proof)
+        .expect("verify should not fail with valid input");
+
+    assert_eq!(prover_public_values, verifier_public_values);
+
+    test_case.assert_output(&verifier_public_values);
+
+    verifier_public_values
+}
+
+/// Executes and proves with cycle limit `1`, then asserts both fail with
+/// [`zkVMError::CycleLimitExceeded`].
+pub fn run_zkvm_cycle_limit_exceeded(zkvm: &impl zkVM, test_case: &impl TestCase) {
+    const CYCLE_LIMIT: u64 = 1;
+
+    let input = test_case.serialized_input();
+
+    let options = ExecuteOptions::default().with_cycle_limit(CYCLE_LIMIT);
+    let err = zkvm.execute_with_options(&input, &options).unwrap_err();
+    assert!(matches!(
+        err,
+        zkVMError::CycleLimitExceeded {
+            cycle_limit: CYCLE_LIMIT
+        }
+    ));
+
+    let options = ProveOptions::default().with_cycle_limit(CYCLE_LIMIT);
+    let err = zkvm
+        .prove_with_options(&input, ProofKind::default(), &options)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        zkVMError::CycleLimitExceeded {
+            cycle_limit: CYCLE_LIMIT
+        }
+    ));
+}
+
+/// Proves by `zkvm` and verifies by `verifier`, which should be created from
+/// the program digest of `zkvm`.
+pub fn run_zkvm_prove_with_verifier(
+    zkvm: &impl zkVM,
+    verifier: &impl zkVMVerifier,
+    test_case: &impl TestCase,
+) -> PublicValues {
     let (prover_public_values, proof, _report) = zkvm
         .prove(&test_case.serialized_input(), ProofKind::default())
         .expect("prove should not fail with valid input");
 
-    let verifier_public_values = zkvm
+    let verifier_public_values = verifier
         .verify(&proof)
         .expect("verify should not fail with valid input");
 
@@ -41,6 +115,33 @@ pub fn run_zkvm_prove(zkvm: &impl zkVM, test_case: &impl TestCase) -> PublicValu
     verifier_public_values
 }
 
+/// Proves with `mock_zkvm` created in [`ProverResourceType::Mock`] mode, and
+/// checks the mock proof is only accepted by `mock_zkvm` but not `zkvm`.
+///
+/// [`ProverResourceType::Mock`]: ere_zkvm_interface::zkvm::ProverResourceType::Mock
+pub fn run_zkvm_prove_mock(
+    mock_zkvm: &impl zkVM,
+    zkvm: &impl zkVM,
+    test_case: &impl TestCase,
+) -> PublicValues {
+    let (prover_public_values, proof, _report) = mock_zkvm
+        .prove(&test_case.serialized_input(), ProofKind::default())
+        .expect("prove should not fail with valid input");
+
+    let verifier_public_values = mock_zkvm
+        .verify(&proof)
+        .expect("verify should not fail with mock proof in mock mode");
+
+    assert_eq!(prover_public_values, verifier_public_values);
+
+    zkvm.verify(&proof)
+        .expect_err("verify should fail with mock proof in non-mock mode");
+
+    test_case.assert_output(&verifier_public_values);
+
+    verifier_public_values
+}
+
 /// Test case for specific [`Program`] that provides serialized
 /// [`Program::Input`], and is able to assert if the [`PublicValues`] returned
 /// by [`zkVM`] methods is correct or not.
@@ -17,7 +17,10 @@ pub trait Program {
     fn run<P: Platform>() {
         let io_serde = Self::io_serde();
         let input = io_serde.deserialize(&P::read_input()).unwrap();
-        let output = io_serde.serialize(&Self::compute(input)).unwrap();
+        P::region_start("compute");
+        let output = Self::compute(input);
+        P::region_end("compute");
+        let output = io_serde.serialize(&output).unwrap();
         P::write_output(&output);
     }
 }
@@ -2,15 +2,35 @@
 
 use serde::{Serialize, de::DeserializeOwned};
 
+mod async_zkvm;
+mod cache;
+mod cancel;
+mod envelope;
 mod error;
+mod memory;
+mod mock;
+mod options;
+mod progress;
 mod proof;
+mod region;
 mod report;
 mod resource;
 
-pub use error::CommonError;
+pub use async_zkvm::AsynczkVM;
+#[cfg(feature = "tokio")]
+pub use async_zkvm::BlockingzkVM;
+pub use cache::{KEY_CACHE_DIR_ENV, KeyCache};
+pub use cancel::{Cancellation, CancellationToken};
+pub use envelope::{PROOF_ENVELOPE_VERSION, ProofEnvelope};
+pub use error::{CommonError, zkVMError};
+pub use memory::{peak_memory, reset_peak_memory};
+pub use mock::{MOCK_PROOF_MAGIC, mock_prove, mock_verify};
+pub use options::{ExecuteOptions, ProveOptions};
+pub use progress::{ProgressCallback, ProvePhase, ProveProgress, SegmentProgress};
 pub use proof::{Proof, ProofKind};
-pub use report::{ProgramExecutionReport, ProgramProvingReport};
-pub use resource::{NetworkProverConfig, ProverResourceType};
+pub use region::{REGION_END_MARKER, REGION_START_MARKER, region_cycles};
+pub use report::{GuestOutput, ProgramExecutionReport, ProgramProvingReport};
+pub use resource::{NetworkProverConfig, ProverResourceKind, ProverResourceType};
 
 /// Public values committed/revealed by guest program.
 ///
@@ -29,19 +49,68 @@ pub type PublicValues = Vec<u8>;
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait zkVM {
     /// Executes the program with the given input.
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)>;
+    fn execute(&self, input: &[u8]) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        self.execute_with_options(input, &ExecuteOptions::default())
+    }
+
+    /// Executes the program with the given input and [`ExecuteOptions`].
+    ///
+    /// Returns [`CommonError::CycleLimitExceeded`] if the execution exceeds
+    /// [`ExecuteOptions::cycle_limit`].
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError>;
 
     /// Creates a proof of the program execution with given input.
     fn prove(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)>;
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        self.prove_with_options(input, proof_kind, &ProveOptions::default())
+    }
+
+    /// Creates a proof of the program execution with given input and
+    /// [`ProveOptions`].
+    ///
+    /// Returns [`CommonError::CycleLimitExceeded`] if the execution exceeds
+    /// [`ProveOptions::cycle_limit`], and [`zkVMError::Cancelled`] if the
+    /// proving is cancelled by [`ProveOptions::cancellation_token`] or exceeds
+    /// [`ProveOptions::timeout`] (not supported by all zkVMs).
+    fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError>;
 
     /// Verifies a proof of the program used to create this zkVM instance, then
     /// returns the public values extracted from the proof.
     #[must_use = "Public values must be used"]
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues>;
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError>;
+
+    /// Returns the name of the zkVM
+    fn name(&self) -> &'static str;
+
+    /// Returns the version of the zkVM SDK (e.g. 0.1.0)
+    fn sdk_version(&self) -> &'static str;
+}
+
+/// Verifier-only counterpart of [`zkVM`].
+///
+/// A verifier instance is created from [`zkVMProgramDigest::ProgramDigest`]
+/// only, so it doesn't need the compiled program nor do any proving setup,
+/// which makes it cheap to create for checking proofs.
+///
+/// Each zkVM implementation will have their own construction function.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait zkVMVerifier {
+    /// Verifies a proof of the program the verifier is created for, then
+    /// returns the public values extracted from the proof.
+    #[must_use = "Public values must be used"]
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError>;
 
     /// Returns the name of the zkVM
     fn name(&self) -> &'static str;
@@ -55,5 +124,49 @@ pub trait zkVMProgramDigest {
     type ProgramDigest: Clone + Serialize + DeserializeOwned;
 
     /// Returns [`zkVMProgramDigest::ProgramDigest`].
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest>;
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError>;
+
+    /// Returns [`zkVMProgramDigest::ProgramDigest`] serialized with `bincode`.
+    fn program_digest_bytes(&self) -> Result<Vec<u8>, zkVMError> {
+        let program_digest = self.program_digest()?;
+        Ok(
+            bincode::serde::encode_to_vec(&program_digest, bincode::config::legacy())
+                .map_err(|err| CommonError::serialize("program_digest", "bincode", err))?,
+        )
+    }
+}
+
+/// Capabilities of a [`zkVM`] implementation.
+///
+/// The associated constants are available without an instance, so callers can
+/// check whether a [`ProverResourceType`] or [`ProofKind`] is supported before
+/// creating the zkVM instance.
+pub trait zkVMCapabilities {
+    /// Supported [`ProverResourceKind`]s.
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind];
+
+    /// Supported [`ProofKind`]s.
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind];
+
+    /// Returns `Err` if the [`ProverResourceType`] is not supported.
+    fn check_prover_resource(resource: &ProverResourceType) -> Result<(), CommonError> {
+        if !Self::SUPPORTED_PROVER_RESOURCE_KINDS.contains(&resource.kind()) {
+            return Err(CommonError::unsupported_prover_resource_kind(
+                resource.kind(),
+                Self::SUPPORTED_PROVER_RESOURCE_KINDS.iter().copied(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `Err` if the [`ProofKind`] is not supported.
+    fn check_proof_kind(proof_kind: ProofKind) -> Result<(), CommonError> {
+        if !Self::SUPPORTED_PROOF_KINDS.contains(&proof_kind) {
+            return Err(CommonError::unsupported_proof_kind(
+                proof_kind,
+                Self::SUPPORTED_PROOF_KINDS.iter().copied(),
+            ));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,198 @@
+use crate::zkvm::{
+    ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind, ProveOptions,
+    PublicValues, zkVMError,
+};
+use std::future::Future;
+
+#[cfg(feature = "tokio")]
+use crate::zkvm::zkVM;
+#[cfg(feature = "tokio")]
+use std::sync::Arc;
+
+/// Async counterpart of [`zkVM`], for callers that drive zkVMs from an async
+/// runtime and shouldn't block its worker threads.
+///
+/// It's implemented natively by zkVMs that talk to a remote server, and the
+/// in-process zkVMs can be adapted by [`BlockingzkVM`] (requires feature
+/// `tokio`), which runs the methods by [`tokio::task::spawn_blocking`].
+///
+/// [`zkVM`]: crate::zkvm::zkVM
+/// [`tokio::task::spawn_blocking`]: https://docs.rs/tokio/latest/tokio/task/fn.spawn_blocking.html
+pub trait AsynczkVM: Send + Sync {
+    /// Executes the program with the given input.
+    fn execute(
+        &self,
+        input: &[u8],
+    ) -> impl Future<Output = Result<(PublicValues, ProgramExecutionReport), zkVMError>> + Send
+    {
+        async move {
+            self.execute_with_options(input, &ExecuteOptions::default())
+                .await
+        }
+    }
+
+    /// Executes the program with the given input and [`ExecuteOptions`].
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> impl Future<Output = Result<(PublicValues, ProgramExecutionReport), zkVMError>> + Send;
+
+    /// Creates a proof of the program execution with given input.
+    fn prove(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+    ) -> impl Future<Output = Result<(PublicValues, Proof, ProgramProvingReport), zkVMError>> + Send
+    {
+        async move {
+            self.prove_with_options(input, proof_kind, &ProveOptions::default())
+                .await
+        }
+    }
+
+    /// Creates a proof of the program execution with given input and
+    /// [`ProveOptions`].
+    fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> impl Future<Output = Result<(PublicValues, Proof, ProgramProvingReport), zkVMError>> + Send;
+
+    /// Verifies a proof of the program used to create this zkVM instance, then
+    /// returns the public values extracted from the proof.
+    fn verify(&self, proof: &Proof)
+    -> impl Future<Output = Result<PublicValues, zkVMError>> + Send;
+}
+
+/// Adapter that implements [`AsynczkVM`] for a blocking [`zkVM`], by
+/// running each method in [`tokio::task::spawn_blocking`].
+///
+/// It must be used within a tokio runtime.
+#[cfg(feature = "tokio")]
+pub struct BlockingzkVM<T>(Arc<T>);
+
+#[cfg(feature = "tokio")]
+impl<T> Clone for BlockingzkVM<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> BlockingzkVM<T> {
+    pub fn new(zkvm: impl Into<Arc<T>>) -> Self {
+        Self(zkvm.into())
+    }
+
+    /// Returns the inner [`zkVM`].
+    pub fn inner(&self) -> &Arc<T> {
+        &self.0
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: zkVM + Send + Sync + 'static> BlockingzkVM<T> {
+    async fn spawn_blocking<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&T) -> Result<R, zkVMError> + Send + 'static,
+    ) -> Result<R, zkVMError> {
+        let zkvm = self.0.clone();
+        tokio::task::spawn_blocking(move || f(&zkvm))
+            .await
+            .map_err(zkVMError::infrastructure)?
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T: zkVM + Send + Sync + 'static> AsynczkVM for BlockingzkVM<T> {
+    async fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        let (input, options) = (input.to_vec(), options.clone());
+        self.spawn_blocking(move |zkvm| zkvm.execute_with_options(&input, &options))
+            .await
+    }
+
+    async fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        let (input, options) = (input.to_vec(), options.clone());
+        self.spawn_blocking(move |zkvm| zkvm.prove_with_options(&input, proof_kind, &options))
+            .await
+    }
+
+    async fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        let proof = proof.clone();
+        self.spawn_blocking(move |zkvm| zkvm.verify(&proof)).await
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use crate::zkvm::{
+        AsynczkVM, BlockingzkVM, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport,
+        Proof, ProofKind, ProveOptions, PublicValues, zkVM, zkVMError,
+    };
+
+    /// zkVM that "proves" by echoing the input.
+    struct EchozkVM;
+
+    impl zkVM for EchozkVM {
+        fn execute_with_options(
+            &self,
+            input: &[u8],
+            _: &ExecuteOptions,
+        ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+            Ok((input.to_vec(), ProgramExecutionReport::default()))
+        }
+
+        fn prove_with_options(
+            &self,
+            input: &[u8],
+            proof_kind: ProofKind,
+            _: &ProveOptions,
+        ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+            let proof = Proof::new(proof_kind, input.to_vec());
+            Ok((input.to_vec(), proof, ProgramProvingReport::default()))
+        }
+
+        fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+            Ok(proof.as_bytes().to_vec())
+        }
+
+        fn name(&self) -> &'static str {
+            "echo"
+        }
+
+        fn sdk_version(&self) -> &'static str {
+            "0.1.0"
+        }
+    }
+
+    #[test]
+    fn test_blocking_zkvm() {
+        // `spawn_blocking` should work in `current_thread` runtime.
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let zkvm = BlockingzkVM::new(EchozkVM);
+
+        runtime.block_on(async {
+            let (public_values, _) = zkvm.execute(b"input").await.unwrap();
+            assert_eq!(public_values, b"input");
+
+            let (public_values, proof, _) =
+                zkvm.prove(b"input", ProofKind::Compressed).await.unwrap();
+            assert_eq!(public_values, b"input");
+
+            assert_eq!(zkvm.verify(&proof).await.unwrap(), b"input");
+        });
+    }
+}
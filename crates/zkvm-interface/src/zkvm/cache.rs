@@ -0,0 +1,223 @@
+use crate::zkvm::CommonError;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Environment variable of the [`KeyCache`] directory.
+pub const KEY_CACHE_DIR_ENV: &str = "ERE_KEY_CACHE_DIR";
+
+/// Version of `ere` crates, which is shared by all crates in the workspace.
+const ERE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Header written before the cached value, to tell whether the entry is stale.
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
+struct KeyCacheHeader {
+    sdk_version: String,
+    ere_version: String,
+}
+
+/// Opt-in on-disk cache of the proving/verifying keys and preprocessing, which
+/// is expensive to generate but deterministic for the same program and SDK.
+///
+/// Entries are stored at `{dir}/{zkvm_name}/{program_hash}/{id}.bin`, where
+/// `program_hash` is the SHA-256 of the program. Each entry records the SDK and
+/// `ere` version that wrote it, an entry written by other versions or failed
+/// to be deserialized is treated as stale and overwritten on next setup.
+#[derive(Clone, Debug, Default)]
+pub struct KeyCache {
+    dir: Option<PathBuf>,
+}
+
+impl KeyCache {
+    /// Returns [`KeyCache`] stored in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Returns [`KeyCache`] that is disabled, which always does the setup.
+    pub fn disabled() -> Self {
+        Self { dir: None }
+    }
+
+    /// Returns [`KeyCache`] stored in the directory set by env
+    /// [`KEY_CACHE_DIR_ENV`], or disabled if it's not set.
+    pub fn from_env() -> Self {
+        match env::var_os(KEY_CACHE_DIR_ENV) {
+            Some(dir) if !dir.is_empty() => Self::new(dir),
+            _ => Self::disabled(),
+        }
+    }
+
+    /// Returns the cache directory, `None` if the cache is disabled.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns the cached value `id` of the `program`, or calls `setup` to
+    /// generate and then stores it if the entry is missing or stale.
+    pub fn get_or_insert_with<T, E>(
+        &self,
+        zkvm_name: &str,
+        sdk_version: &str,
+        program: &[u8],
+        id: &str,
+        setup: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        E: From<CommonError>,
+    {
+        let Some(dir) = &self.dir else {
+            return setup();
+        };
+
+        let program_hash = Sha256::digest(program)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let entry_dir = dir.join(zkvm_name).join(program_hash);
+        let entry_path = entry_dir.join(format!("{id}.bin"));
+
+        let header = KeyCacheHeader {
+            sdk_version: sdk_version.to_string(),
+            ere_version: ERE_VERSION.to_string(),
+        };
+
+        if let Some(value) = read_entry(&entry_path, &header) {
+            return Ok(value);
+        }
+
+        let value = setup()?;
+
+        fs::create_dir_all(&entry_dir)
+            .map_err(|err| CommonError::create_dir("key cache", &entry_dir, err))?;
+        write_entry(&entry_path, &header, &value)?;
+
+        Ok(value)
+    }
+}
+
+/// Returns the value of entry at `path`, `None` if it's missing or stale.
+fn read_entry<T: DeserializeOwned>(path: &Path, header: &KeyCacheHeader) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    // Limit the header size, so a corrupted entry won't be decoded into a
+    // huge allocation.
+    let header_config = bincode::config::legacy().with_limit::<1024>();
+    let (entry_header, len): (KeyCacheHeader, _) =
+        bincode::serde::decode_from_slice(&bytes, header_config).ok()?;
+    if entry_header != *header {
+        return None;
+    }
+    let (value, _) =
+        bincode::serde::decode_from_slice(&bytes[len..], bincode::config::legacy()).ok()?;
+    Some(value)
+}
+
+/// Writes the entry to a temporary file then renames it to `path`, so other
+/// processes never read a partially written entry.
+fn write_entry<T: Serialize>(
+    path: &Path,
+    header: &KeyCacheHeader,
+    value: &T,
+) -> Result<(), CommonError> {
+    let config = bincode::config::legacy();
+    let mut bytes = bincode::serde::encode_to_vec(header, config)
+        .map_err(|err| CommonError::serialize("key cache header", "bincode", err))?;
+    bytes.extend(
+        bincode::serde::encode_to_vec(value, config)
+            .map_err(|err| CommonError::serialize("key cache entry", "bincode", err))?,
+    );
+
+    let tmp_path = path.with_extension(format!("bin.{}.tmp", process::id()));
+    fs::write(&tmp_path, bytes)
+        .map_err(|err| CommonError::write_file("key cache entry", &tmp_path, err))?;
+    fs::rename(&tmp_path, path).map_err(|err| CommonError::io("Failed to rename key cache", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{CommonError, KeyCache};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    fn setup(value: u64) -> impl FnOnce() -> Result<u64, CommonError> {
+        move || Ok(value)
+    }
+
+    fn no_setup() -> Result<u64, CommonError> {
+        panic!("setup should be skipped on cache hit")
+    }
+
+    fn get(
+        cache: &KeyCache,
+        sdk_version: &str,
+        program: &[u8],
+        setup: impl FnOnce() -> Result<u64, CommonError>,
+    ) -> u64 {
+        cache
+            .get_or_insert_with("zkvm", sdk_version, program, "keys", setup)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = KeyCache::new(tempdir.path());
+
+        // Miss then hit
+        assert_eq!(get(&cache, "0.1.0", b"program", setup(1)), 1);
+        assert_eq!(get(&cache, "0.1.0", b"program", no_setup), 1);
+
+        // Different program
+        assert_eq!(get(&cache, "0.1.0", b"other", setup(2)), 2);
+        assert_eq!(get(&cache, "0.1.0", b"program", no_setup), 1);
+
+        // Stale entry written by other SDK version is overwritten
+        assert_eq!(get(&cache, "0.2.0", b"program", setup(3)), 3);
+        assert_eq!(get(&cache, "0.2.0", b"program", no_setup), 3);
+    }
+
+    #[test]
+    fn test_get_or_insert_with_corrupted() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = KeyCache::new(tempdir.path());
+
+        assert_eq!(get(&cache, "0.1.0", b"program", setup(1)), 1);
+
+        for entry in walk(tempdir.path()) {
+            fs::write(entry, b"corrupted").unwrap();
+        }
+
+        assert_eq!(get(&cache, "0.1.0", b"program", setup(2)), 2);
+        assert_eq!(get(&cache, "0.1.0", b"program", no_setup), 2);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let cache = KeyCache::disabled();
+        assert_eq!(get(&cache, "0.1.0", b"program", setup(1)), 1);
+        assert_eq!(get(&cache, "0.1.0", b"program", setup(2)), 2);
+    }
+
+    fn walk(dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(dir)
+            .unwrap()
+            .flat_map(|entry| {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path)
+                } else {
+                    vec![path]
+                }
+            })
+            .collect()
+    }
+}
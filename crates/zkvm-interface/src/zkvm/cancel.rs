@@ -0,0 +1,231 @@
+use crate::zkvm::CommonError;
+use std::{
+    io::Read,
+    panic,
+    process::{Child, Command, ExitStatus, Output, Stdio},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Interval to poll the [`Cancellation`] while waiting for the proving.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Token to cancel an in-flight proving, set by
+/// [`ProveOptions::with_cancellation_token`].
+///
+/// Clones share the same state, so the caller keeps a clone and calls
+/// [`CancellationToken::cancel`] from another thread.
+///
+/// [`ProveOptions::with_cancellation_token`]: crate::zkvm::ProveOptions::with_cancellation_token
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the proving that uses this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cancellation state of a proving, created by [`ProveOptions::cancellation`]
+/// when the proving starts, so the timeout counts from there.
+///
+/// [`ProveOptions::cancellation`]: crate::zkvm::ProveOptions::cancellation
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation {
+    token: Option<CancellationToken>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl Cancellation {
+    pub(crate) fn new(token: Option<CancellationToken>, timeout: Option<Duration>) -> Self {
+        Self {
+            token,
+            deadline: timeout.map(|timeout| (Instant::now() + timeout, timeout)),
+        }
+    }
+
+    /// Returns [`CommonError::Cancelled`] if the token is cancelled, or
+    /// [`CommonError::Timeout`] if the deadline has passed.
+    pub fn check(&self) -> Result<(), CommonError> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(|token| token.is_cancelled())
+        {
+            return Err(CommonError::Cancelled);
+        }
+        match self.deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => {
+                Err(CommonError::Timeout { timeout })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns whether there is nothing to cancel the proving.
+    pub fn is_never(&self) -> bool {
+        self.token.is_none() && self.deadline.is_none()
+    }
+
+    /// Same as [`Command::output`], but kills the child process if the
+    /// proving is cancelled before it exits.
+    pub fn output(&self, cmd: &mut Command) -> Result<Output, CommonError> {
+        self.check()?;
+
+        if self.is_never() {
+            return cmd.output().map_err(|err| CommonError::command(cmd, err));
+        }
+
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| CommonError::command(cmd, err))?;
+
+        // Drain the pipes in background, otherwise the child blocks once the
+        // pipe buffer is full.
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let status = self.wait(cmd, &mut child);
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        Ok(Output {
+            status: status?,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Runs `f` in another thread, and calls `abort` if the proving is
+    /// cancelled before `f` returns.
+    ///
+    /// `abort` should make `f` return early (e.g. by killing the process `f`
+    /// waits for), it returns [`CommonError::Cancelled`] or
+    /// [`CommonError::Timeout`] after `f` returns.
+    pub fn run<T: Send>(
+        &self,
+        f: impl FnOnce() -> T + Send,
+        abort: impl FnOnce(),
+    ) -> Result<T, CommonError> {
+        self.check()?;
+
+        if self.is_never() {
+            return Ok(f());
+        }
+
+        thread::scope(|scope| {
+            let handle = scope.spawn(f);
+            let mut result = Ok(());
+            while !handle.is_finished() {
+                if let Err(err) = self.check() {
+                    result = Err(err);
+                    break;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+            if result.is_err() {
+                abort();
+            }
+            let output = handle
+                .join()
+                .unwrap_or_else(|err| panic::resume_unwind(err));
+            result.map(|_| output)
+        })
+    }
+
+    /// Waits for the child to exit, kills it if the proving is cancelled.
+    fn wait(&self, cmd: &Command, child: &mut Child) -> Result<ExitStatus, CommonError> {
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return Ok(status),
+                Ok(None) => {}
+                Err(err) => return Err(CommonError::command(cmd, err)),
+            }
+            if let Err(err) = self.check() {
+                child
+                    .kill()
+                    .and_then(|_| child.wait())
+                    .map_err(|err| CommonError::command(cmd, err))?;
+                return Err(err);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// Reads the pipe to end in another thread.
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{CommonError, ProveOptions};
+    use std::{process::Command, thread, time::Duration};
+
+    #[test]
+    fn test_cancel_output() {
+        let options = ProveOptions::default().with_timeout(Duration::from_millis(200));
+        let cancellation = options.cancellation();
+        let err = cancellation
+            .output(Command::new("sleep").arg("10"))
+            .unwrap_err();
+        assert!(matches!(err, CommonError::Timeout { .. }));
+
+        let output = ProveOptions::default()
+            .with_timeout(Duration::from_secs(10))
+            .cancellation()
+            .output(Command::new("echo").arg("ere"))
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"ere\n");
+    }
+
+    #[test]
+    fn test_cancel_run() {
+        let options = ProveOptions::default().with_cancellation_token(Default::default());
+        let token = options.cancellation_token.clone().unwrap();
+        let cancellation = options.cancellation();
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(200));
+                token.cancel();
+            });
+
+            let (tx, rx) = std::sync::mpsc::channel();
+            let err = cancellation
+                .run(move || rx.recv().unwrap(), move || tx.send(()).unwrap())
+                .unwrap_err();
+            assert!(matches!(err, CommonError::Cancelled));
+        });
+
+        // Cancelled before start
+        assert!(matches!(
+            cancellation.run(|| (), || ()),
+            Err(CommonError::Cancelled)
+        ));
+    }
+}
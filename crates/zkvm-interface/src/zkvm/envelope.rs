@@ -0,0 +1,303 @@
+use crate::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVM, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+use serde::{Deserialize, Serialize};
+
+/// Current version of [`ProofEnvelope`] format.
+pub const PROOF_ENVELOPE_VERSION: u32 = 1;
+
+/// Version of `ere` crates, which is shared by all crates in the workspace.
+const ERE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Self-describing [`Proof`] that records which zkVM, SDK version and program
+/// produced it, so it can be stored and verified later.
+///
+/// Use [`ProofEnvelope::seal`] to create one from the zkVM instance that
+/// proved it, and [`ProofEnvelope::verify`] to check it against the zkVM
+/// instance before verifying the inner [`Proof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofEnvelope {
+    /// Version of the envelope format.
+    pub version: u32,
+    /// Name of the zkVM returned by [`zkVM::name`].
+    pub zkvm_name: String,
+    /// Version of the zkVM SDK returned by [`zkVM::sdk_version`].
+    pub sdk_version: String,
+    /// Version of `ere` crates that created the envelope.
+    pub ere_version: String,
+    /// Program digest returned by [`zkVMProgramDigest::program_digest_bytes`].
+    pub program_digest: Vec<u8>,
+    /// The proof generated by [`zkVM::prove`].
+    pub proof: Proof,
+}
+
+impl ProofEnvelope {
+    /// Wraps the `proof` generated by `zkvm` into [`ProofEnvelope`].
+    pub fn seal(zkvm: &(impl zkVM + zkVMProgramDigest), proof: Proof) -> Result<Self, zkVMError> {
+        Ok(Self {
+            version: PROOF_ENVELOPE_VERSION,
+            zkvm_name: zkvm.name().to_string(),
+            sdk_version: zkvm.sdk_version().to_string(),
+            ere_version: ERE_VERSION.to_string(),
+            program_digest: zkvm.program_digest_bytes()?,
+            proof,
+        })
+    }
+
+    /// Returns [`ProofKind`] of the inner proof.
+    pub fn kind(&self) -> ProofKind {
+        self.proof.kind()
+    }
+
+    /// Checks the envelope is created by the same zkVM, SDK version and
+    /// program as `zkvm`, then returns the inner [`Proof`].
+    pub fn open(&self, zkvm: &(impl zkVM + zkVMProgramDigest)) -> Result<&Proof, zkVMError> {
+        self.check(
+            zkvm.name(),
+            zkvm.sdk_version(),
+            zkvm.program_digest_bytes()?,
+        )
+    }
+
+    /// Checks the envelope by [`ProofEnvelope::open`], then verifies the inner
+    /// [`Proof`] and returns the public values.
+    pub fn verify(
+        &self,
+        zkvm: &(impl zkVM + zkVMProgramDigest),
+    ) -> Result<PublicValues, zkVMError> {
+        zkvm.verify(self.open(zkvm)?)
+    }
+
+    /// Same as [`ProofEnvelope::open`] but checks against a [`zkVMVerifier`].
+    pub fn open_with_verifier(
+        &self,
+        verifier: &(impl zkVMVerifier + zkVMProgramDigest),
+    ) -> Result<&Proof, zkVMError> {
+        self.check(
+            verifier.name(),
+            verifier.sdk_version(),
+            verifier.program_digest_bytes()?,
+        )
+    }
+
+    /// Same as [`ProofEnvelope::verify`] but verifies by a [`zkVMVerifier`].
+    pub fn verify_with_verifier(
+        &self,
+        verifier: &(impl zkVMVerifier + zkVMProgramDigest),
+    ) -> Result<PublicValues, zkVMError> {
+        verifier.verify(self.open_with_verifier(verifier)?)
+    }
+
+    fn check(
+        &self,
+        zkvm_name: &str,
+        sdk_version: &str,
+        program_digest: Vec<u8>,
+    ) -> Result<&Proof, zkVMError> {
+        if self.version != PROOF_ENVELOPE_VERSION {
+            return Err(CommonError::UnsupportedProofEnvelopeVersion(self.version).into());
+        }
+
+        if self.zkvm_name != zkvm_name {
+            return Err(
+                CommonError::proof_envelope_mismatch("zkVM", zkvm_name, &self.zkvm_name).into(),
+            );
+        }
+
+        if self.sdk_version != sdk_version {
+            return Err(CommonError::proof_envelope_mismatch(
+                "SDK version",
+                sdk_version,
+                &self.sdk_version,
+            )
+            .into());
+        }
+
+        if self.program_digest != program_digest {
+            return Err(CommonError::proof_envelope_mismatch(
+                "program digest",
+                to_hex(&program_digest),
+                to_hex(&self.program_digest),
+            )
+            .into());
+        }
+
+        Ok(&self.proof)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{
+        CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof,
+        ProofEnvelope, ProofKind, ProveOptions, PublicValues, zkVM, zkVMError, zkVMProgramDigest,
+        zkVMVerifier,
+    };
+
+    /// Mock zkVM that "proves" by echoing the input.
+    struct MockzkVM {
+        name: &'static str,
+        sdk_version: &'static str,
+        program_digest: [u8; 32],
+    }
+
+    impl Default for MockzkVM {
+        fn default() -> Self {
+            Self {
+                name: "mock",
+                sdk_version: "0.1.0",
+                program_digest: [0; 32],
+            }
+        }
+    }
+
+    impl zkVM for MockzkVM {
+        fn execute_with_options(
+            &self,
+            input: &[u8],
+            _: &ExecuteOptions,
+        ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+            Ok((input.to_vec(), ProgramExecutionReport::default()))
+        }
+
+        fn prove_with_options(
+            &self,
+            input: &[u8],
+            proof_kind: ProofKind,
+            _: &ProveOptions,
+        ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+            let proof = Proof::new(proof_kind, input.to_vec());
+            Ok((input.to_vec(), proof, ProgramProvingReport::default()))
+        }
+
+        fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+            Ok(proof.as_bytes().to_vec())
+        }
+
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn sdk_version(&self) -> &'static str {
+            self.sdk_version
+        }
+    }
+
+    impl zkVMProgramDigest for MockzkVM {
+        type ProgramDigest = [u8; 32];
+
+        fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+            Ok(self.program_digest)
+        }
+    }
+
+    /// Mock verifier of [`MockzkVM`].
+    struct MockVerifier(MockzkVM);
+
+    impl zkVMVerifier for MockVerifier {
+        fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+            zkVM::verify(&self.0, proof)
+        }
+
+        fn name(&self) -> &'static str {
+            self.0.name
+        }
+
+        fn sdk_version(&self) -> &'static str {
+            self.0.sdk_version
+        }
+    }
+
+    impl zkVMProgramDigest for MockVerifier {
+        type ProgramDigest = [u8; 32];
+
+        fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+            Ok(self.0.program_digest)
+        }
+    }
+
+    fn sealed_envelope() -> ProofEnvelope {
+        let zkvm = MockzkVM::default();
+        let (_, proof, _) = zkvm.prove(b"input", ProofKind::Compressed).unwrap();
+        ProofEnvelope::seal(&zkvm, proof).unwrap()
+    }
+
+    fn assert_mismatch(envelope: &ProofEnvelope, zkvm: &MockzkVM, expected_field: &str) {
+        let zkVMError::InvalidProof(err) = envelope.verify(zkvm).unwrap_err() else {
+            panic!("Mismatch should be invalid proof");
+        };
+        assert!(matches!(
+            err.downcast::<CommonError>().unwrap(),
+            CommonError::ProofEnvelopeMismatch { field, .. } if field == expected_field
+        ));
+    }
+
+    #[test]
+    fn test_verify() {
+        let envelope = sealed_envelope();
+
+        // Roundtrip through serialization as it would be stored on disk.
+        let bytes = bincode::serde::encode_to_vec(&envelope, bincode::config::legacy()).unwrap();
+        let (envelope, _): (ProofEnvelope, _) =
+            bincode::serde::decode_from_slice(&bytes, bincode::config::legacy()).unwrap();
+
+        assert_eq!(envelope.kind(), ProofKind::Compressed);
+        assert_eq!(envelope.verify(&MockzkVM::default()).unwrap(), b"input");
+    }
+
+    #[test]
+    fn test_verify_with_verifier() {
+        let envelope = sealed_envelope();
+
+        let verifier = MockVerifier(MockzkVM::default());
+        assert_eq!(envelope.verify_with_verifier(&verifier).unwrap(), b"input");
+
+        let verifier = MockVerifier(MockzkVM {
+            program_digest: [1; 32],
+            ..Default::default()
+        });
+        envelope.verify_with_verifier(&verifier).unwrap_err();
+    }
+
+    #[test]
+    fn test_verify_mismatch() {
+        let envelope = sealed_envelope();
+
+        let zkvm = MockzkVM {
+            name: "other",
+            ..Default::default()
+        };
+        assert_mismatch(&envelope, &zkvm, "zkVM");
+
+        let zkvm = MockzkVM {
+            sdk_version: "0.2.0",
+            ..Default::default()
+        };
+        assert_mismatch(&envelope, &zkvm, "SDK version");
+
+        let zkvm = MockzkVM {
+            program_digest: [1; 32],
+            ..Default::default()
+        };
+        assert_mismatch(&envelope, &zkvm, "program digest");
+    }
+
+    #[test]
+    fn test_verify_unsupported_version() {
+        let mut envelope = sealed_envelope();
+        envelope.version += 1;
+
+        let zkVMError::InvalidProof(err) = envelope.verify(&MockzkVM::default()).unwrap_err()
+        else {
+            panic!("Unsupported version should be invalid proof");
+        };
+        assert!(matches!(
+            err.downcast::<CommonError>().unwrap(),
+            CommonError::UnsupportedProofEnvelopeVersion(_)
+        ));
+    }
+}
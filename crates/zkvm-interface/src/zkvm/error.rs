@@ -1,11 +1,107 @@
-use crate::zkvm::ProofKind;
+use crate::zkvm::{PROOF_ENVELOPE_VERSION, ProofKind, ProverResourceKind};
 use std::{
     io,
     path::Path,
     process::{Command, ExitStatus, Output},
+    time::Duration,
 };
 use thiserror::Error;
 
+/// Error returned by [`zkVM`], [`zkVMVerifier`] and [`zkVMProgramDigest`]
+/// methods.
+///
+/// It's categorized by the cause, so callers can tell for example a bad input
+/// that will always fail from a prover failure that might succeed on retry.
+///
+/// [`zkVM`]: crate::zkvm::zkVM
+/// [`zkVMVerifier`]: crate::zkvm::zkVMVerifier
+/// [`zkVMProgramDigest`]: crate::zkvm::zkVMProgramDigest
+#[derive(Debug, Error)]
+#[allow(non_camel_case_types)]
+pub enum zkVMError {
+    /// Guest program panicked or failed during execution, with the exit code
+    /// if the zkVM exposes it.
+    #[error("Guest panicked{exit_code}: {message}",
+        exit_code = exit_code.map(|code| format!(" with exit code {code}")).unwrap_or_default())]
+    GuestPanic {
+        message: String,
+        exit_code: Option<i32>,
+    },
+
+    /// Input is not encoded in the way the zkVM expects.
+    #[error("Invalid input: {0}")]
+    InvalidInput(#[source] anyhow::Error),
+
+    /// Execution exceeded the cycle limit in options.
+    #[error("Execution exceeded cycle limit {cycle_limit}")]
+    CycleLimitExceeded { cycle_limit: u64 },
+
+    /// Proof is malformed, of unexpected kind, or fails the verification.
+    #[error("Invalid proof: {0}")]
+    InvalidProof(#[source] anyhow::Error),
+
+    /// Requested proof kind, prover resource or option is not supported.
+    #[error(transparent)]
+    Unsupported(anyhow::Error),
+
+    /// Proving is cancelled by the [`CancellationToken`] or exceeded the
+    /// timeout in options.
+    ///
+    /// [`CancellationToken`]: crate::zkvm::CancellationToken
+    #[error(transparent)]
+    Cancelled(anyhow::Error),
+
+    /// Failure of the zkVM SDK, prover or the environment it runs in.
+    #[error(transparent)]
+    Infrastructure(anyhow::Error),
+}
+
+impl zkVMError {
+    pub fn guest_panic(message: impl ToString, exit_code: Option<i32>) -> Self {
+        Self::GuestPanic {
+            message: message.to_string(),
+            exit_code,
+        }
+    }
+
+    pub fn invalid_input(err: impl Into<anyhow::Error>) -> Self {
+        Self::InvalidInput(err.into())
+    }
+
+    pub fn invalid_proof(err: impl Into<anyhow::Error>) -> Self {
+        Self::InvalidProof(err.into())
+    }
+
+    pub fn unsupported(err: impl Into<anyhow::Error>) -> Self {
+        Self::Unsupported(err.into())
+    }
+
+    pub fn cancelled(err: impl Into<anyhow::Error>) -> Self {
+        Self::Cancelled(err.into())
+    }
+
+    pub fn infrastructure(err: impl Into<anyhow::Error>) -> Self {
+        Self::Infrastructure(err.into())
+    }
+}
+
+impl From<CommonError> for zkVMError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::CycleLimitExceeded { cycle_limit } => {
+                Self::CycleLimitExceeded { cycle_limit }
+            }
+            CommonError::UnsupportedProofKind { .. }
+            | CommonError::UnsupportedProverResourceKind { .. } => Self::unsupported(err),
+            CommonError::UnsupportedProofEnvelopeVersion(_)
+            | CommonError::ProofEnvelopeMismatch { .. }
+            | CommonError::InvalidMockProof => Self::invalid_proof(err),
+            CommonError::Cancelled | CommonError::Timeout { .. } => Self::cancelled(err),
+            _ => Self::infrastructure(err),
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum CommonError {
     #[error("{ctx}: {err}")]
@@ -53,6 +149,34 @@ pub enum CommonError {
         unsupported: ProofKind,
         supported: Vec<ProofKind>,
     },
+
+    #[error("Unsupported proof envelope version {0}, expect {PROOF_ENVELOPE_VERSION}")]
+    UnsupportedProofEnvelopeVersion(u32),
+
+    #[error("Proof envelope {field} mismatch, expect {expected}, got {got}")]
+    ProofEnvelopeMismatch {
+        field: String,
+        expected: String,
+        got: String,
+    },
+
+    #[error("Execution exceeded cycle limit {cycle_limit}")]
+    CycleLimitExceeded { cycle_limit: u64 },
+
+    #[error("Unsupported prover resource kind {unsupported:?}, expect one of {supported:?}")]
+    UnsupportedProverResourceKind {
+        unsupported: ProverResourceKind,
+        supported: Vec<ProverResourceKind>,
+    },
+
+    #[error("Invalid mock proof, only mock proof is accepted in mock mode")]
+    InvalidMockProof,
+
+    #[error("Proving is cancelled")]
+    Cancelled,
+
+    #[error("Proving timed out after {timeout:?}")]
+    Timeout { timeout: Duration },
 }
 
 impl CommonError {
@@ -134,6 +258,15 @@ impl CommonError {
         }
     }
 
+    /// Returns the exit code of [`CommonError::CommandExitNonZero`], `None`
+    /// for other errors or if the command is terminated by signal.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            Self::CommandExitNonZero { status, .. } => status.code(),
+            _ => None,
+        }
+    }
+
     pub fn unsupported_proof_kind(
         unsupported: ProofKind,
         supported: impl IntoIterator<Item = ProofKind>,
@@ -143,4 +276,65 @@ impl CommonError {
             supported: supported.into_iter().collect(),
         }
     }
+
+    pub fn unsupported_prover_resource_kind(
+        unsupported: ProverResourceKind,
+        supported: impl IntoIterator<Item = ProverResourceKind>,
+    ) -> Self {
+        Self::UnsupportedProverResourceKind {
+            unsupported,
+            supported: supported.into_iter().collect(),
+        }
+    }
+
+    pub fn proof_envelope_mismatch(
+        field: impl AsRef<str>,
+        expected: impl ToString,
+        got: impl ToString,
+    ) -> Self {
+        Self::ProofEnvelopeMismatch {
+            field: field.as_ref().to_string(),
+            expected: expected.to_string(),
+            got: got.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{CommonError, ProofKind, zkVMError};
+    use std::io;
+
+    #[test]
+    fn test_from_common_error() {
+        let err = CommonError::CycleLimitExceeded { cycle_limit: 1 };
+        assert!(matches!(
+            zkVMError::from(err),
+            zkVMError::CycleLimitExceeded { cycle_limit: 1 }
+        ));
+
+        let err = CommonError::unsupported_proof_kind(ProofKind::Groth16, [ProofKind::Compressed]);
+        assert!(matches!(zkVMError::from(err), zkVMError::Unsupported(_)));
+
+        let err = CommonError::UnsupportedProofEnvelopeVersion(0);
+        assert!(matches!(zkVMError::from(err), zkVMError::InvalidProof(_)));
+
+        let err = CommonError::Cancelled;
+        assert!(matches!(zkVMError::from(err), zkVMError::Cancelled(_)));
+
+        let err = CommonError::tempdir(io::ErrorKind::Other.into());
+        assert!(matches!(zkVMError::from(err), zkVMError::Infrastructure(_)));
+    }
+
+    #[test]
+    fn test_guest_panic_display() {
+        let err = zkVMError::guest_panic("assertion failed", Some(1));
+        assert_eq!(
+            err.to_string(),
+            "Guest panicked with exit code 1: assertion failed"
+        );
+
+        let err = zkVMError::guest_panic("assertion failed", None);
+        assert_eq!(err.to_string(), "Guest panicked: assertion failed");
+    }
 }
@@ -0,0 +1,45 @@
+use std::fs;
+
+/// Resets the peak resident memory of the current process, so the following
+/// [`peak_memory`] only reflects the usage after this call.
+///
+/// It writes `5` to `/proc/self/clear_refs`, which is only supported on Linux,
+/// otherwise it's a no-op.
+pub fn reset_peak_memory() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// Returns the peak resident memory of the current process in bytes.
+///
+/// It reads `VmHWM` from `/proc/self/status`, so returns `None` if it's not on
+/// Linux.
+///
+/// The peak is process-wide state, so the measurement is only accurate when a
+/// single proving runs in the process at a time. Concurrent provings reset the
+/// peak of each other, and other work in the process is counted in as well.
+pub fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{peak_memory, reset_peak_memory};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_peak_memory() {
+        reset_peak_memory();
+
+        let buf = std::hint::black_box(vec![1u8; 64 << 20]);
+        assert!(peak_memory().unwrap() >= buf.len() as u64);
+    }
+}
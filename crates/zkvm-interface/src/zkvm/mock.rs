@@ -0,0 +1,116 @@
+use crate::zkvm::{
+    CommonError, ProgramProvingReport, Proof, ProofKind, ProveOptions, ProvePhase, PublicValues,
+    zkVM, zkVMError,
+};
+use std::time::Instant;
+
+/// Magic bytes prefixed to the fake proof created by [`mock_prove`], so it
+/// won't be mistaken for a real proof.
+pub const MOCK_PROOF_MAGIC: &[u8] = b"ere-mock-proof:";
+
+/// Executes the program and creates a fake proof that carries the public
+/// values only.
+///
+/// It's used by zkVMs without native mock prover in
+/// [`ProverResourceType::Mock`] mode.
+///
+/// [`ProverResourceType::Mock`]: crate::zkvm::ProverResourceType::Mock
+pub fn mock_prove(
+    zkvm: &impl zkVM,
+    input: &[u8],
+    proof_kind: ProofKind,
+    options: &ProveOptions,
+) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+    options.report_progress(ProvePhase::Execute);
+
+    let start = Instant::now();
+    let (public_values, _) = zkvm.execute_with_options(input, &options.execute_options())?;
+    let proving_time = start.elapsed();
+
+    let proof = Proof::new(proof_kind, [MOCK_PROOF_MAGIC, &public_values].concat());
+    let report = ProgramProvingReport::new(proving_time).with_proof_size(&proof);
+
+    Ok((public_values, proof, report))
+}
+
+/// Verifies the fake proof created by [`mock_prove`] and returns the public
+/// values it carries.
+pub fn mock_verify(proof: &Proof) -> Result<PublicValues, zkVMError> {
+    match proof.as_bytes().strip_prefix(MOCK_PROOF_MAGIC) {
+        Some(public_values) => Ok(public_values.to_vec()),
+        None => Err(CommonError::InvalidMockProof.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{
+        ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
+        ProveOptions, ProvePhase, ProveProgress, PublicValues, mock_prove, mock_verify, zkVM,
+        zkVMError,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// zkVM that executes by echoing the input and only proves in mock mode.
+    struct EchozkVM;
+
+    impl zkVM for EchozkVM {
+        fn execute_with_options(
+            &self,
+            input: &[u8],
+            _: &ExecuteOptions,
+        ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+            Ok((input.to_vec(), ProgramExecutionReport::default()))
+        }
+
+        fn prove_with_options(
+            &self,
+            input: &[u8],
+            proof_kind: ProofKind,
+            options: &ProveOptions,
+        ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+            mock_prove(self, input, proof_kind, options)
+        }
+
+        fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+            mock_verify(proof)
+        }
+
+        fn name(&self) -> &'static str {
+            "echo"
+        }
+
+        fn sdk_version(&self) -> &'static str {
+            "0.1.0"
+        }
+    }
+
+    #[test]
+    fn test_mock_prove_and_verify() {
+        let (public_values, proof, report) = EchozkVM.prove(b"input", ProofKind::Groth16).unwrap();
+        assert_eq!(public_values, b"input");
+        assert_eq!(proof.kind(), ProofKind::Groth16);
+        assert_eq!(report.proof_size, proof.as_bytes().len() as u64);
+        assert_eq!(EchozkVM.verify(&proof).unwrap(), b"input");
+
+        let proof = Proof::Compressed(b"input".to_vec());
+        assert!(matches!(
+            EchozkVM.verify(&proof).unwrap_err(),
+            zkVMError::InvalidProof(_)
+        ));
+    }
+
+    #[test]
+    fn test_mock_prove_progress() {
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let options = ProveOptions::default().with_progress({
+            let phases = phases.clone();
+            move |progress: ProveProgress| phases.lock().unwrap().push(progress.phase)
+        });
+
+        EchozkVM
+            .prove_with_options(b"input", ProofKind::Compressed, &options)
+            .unwrap();
+        assert_eq!(*phases.lock().unwrap(), [ProvePhase::Execute]);
+    }
+}
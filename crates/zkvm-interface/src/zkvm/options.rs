@@ -0,0 +1,147 @@
+use crate::zkvm::{
+    Cancellation, CancellationToken, CommonError, ProgressCallback, ProvePhase, ProveProgress,
+    SegmentProgress,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Options of [`zkVM::execute_with_options`].
+///
+/// [`zkVM::execute_with_options`]: crate::zkvm::zkVM::execute_with_options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecuteOptions {
+    /// Maximum number of cycles the guest program is allowed to run, `None`
+    /// means unlimited.
+    ///
+    /// It's advisory for Jolt, Nexus and Pico, which can only check it after
+    /// the execution finishes, so a guest that never halts isn't stopped.
+    pub cycle_limit: Option<u64>,
+}
+
+impl ExecuteOptions {
+    pub fn with_cycle_limit(mut self, cycle_limit: u64) -> Self {
+        self.cycle_limit = Some(cycle_limit);
+        self
+    }
+
+    /// Returns [`CommonError::CycleLimitExceeded`] if `cycles` exceeds the
+    /// [`ExecuteOptions::cycle_limit`].
+    ///
+    /// It is used to check the limit after execution, for zkVMs that don't
+    /// support limit natively or round it.
+    pub fn check_cycles(&self, cycles: u64) -> Result<(), CommonError> {
+        match self.cycle_limit {
+            Some(cycle_limit) if cycles > cycle_limit => {
+                Err(CommonError::CycleLimitExceeded { cycle_limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Options of [`zkVM::prove_with_options`].
+///
+/// [`zkVM::prove_with_options`]: crate::zkvm::zkVM::prove_with_options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProveOptions {
+    /// Maximum number of cycles the guest program is allowed to run, `None`
+    /// means unlimited.
+    pub cycle_limit: Option<u64>,
+    /// Maximum duration the proving is allowed to take, `None` means
+    /// unlimited.
+    ///
+    /// Like [`ProveOptions::cancellation_token`], it's only honoured by zkVMs
+    /// that prove in a subprocess or remotely, others ignore it.
+    pub timeout: Option<Duration>,
+    /// Token to cancel the proving from another thread.
+    ///
+    /// It's not serialized, remote zkVMs cancel by their own request instead.
+    #[serde(skip)]
+    pub cancellation_token: Option<CancellationToken>,
+    /// Callback to receive the [`ProveProgress`] changes.
+    ///
+    /// It's not serialized, remote zkVMs poll the progress by their own
+    /// request instead.
+    #[serde(skip)]
+    pub progress: Option<ProgressCallback>,
+}
+
+impl ProveOptions {
+    pub fn with_cycle_limit(mut self, cycle_limit: u64) -> Self {
+        self.cycle_limit = Some(cycle_limit);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = Some(cancellation_token);
+        self
+    }
+
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(ProveProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    /// Reports the [`ProvePhase`] to [`ProveOptions::progress`] if it's set.
+    pub fn report_progress(&self, phase: ProvePhase) {
+        if let Some(progress) = &self.progress {
+            progress.report(phase);
+        }
+    }
+
+    /// Reports [`ProvePhase::Prove`] with the number of segments proved to
+    /// [`ProveOptions::progress`] if it's set.
+    pub fn report_segments(&self, proved: u64, total: u64) {
+        if let Some(progress) = &self.progress {
+            progress.report(ProveProgress {
+                phase: ProvePhase::Prove,
+                segments: Some(SegmentProgress { proved, total }),
+            });
+        }
+    }
+
+    /// Returns [`Cancellation`] of the token and timeout, which should be
+    /// called when the proving starts.
+    ///
+    /// zkVMs that prove in a subprocess use it to kill the subprocess when
+    /// the proving is cancelled.
+    pub fn cancellation(&self) -> Cancellation {
+        Cancellation::new(self.cancellation_token.clone(), self.timeout)
+    }
+
+    /// Returns [`ExecuteOptions`] with the same limit.
+    ///
+    /// It is used to execute before proving for zkVMs that don't support limit
+    /// natively when proving.
+    pub fn execute_options(&self) -> ExecuteOptions {
+        ExecuteOptions {
+            cycle_limit: self.cycle_limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{CommonError, ExecuteOptions};
+
+    #[test]
+    fn test_check_cycles() {
+        let options = ExecuteOptions::default();
+        assert!(options.check_cycles(u64::MAX).is_ok());
+
+        let options = ExecuteOptions::default().with_cycle_limit(100);
+        assert!(options.check_cycles(100).is_ok());
+        assert!(matches!(
+            options.check_cycles(101),
+            Err(CommonError::CycleLimitExceeded { cycle_limit: 100 })
+        ));
+    }
+}
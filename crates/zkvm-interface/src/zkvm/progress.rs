@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+use strum::FromRepr;
+
+/// Phase of an in-flight proving, reported by [`ProgressCallback`].
+///
+/// zkVMs only report the phases they go through and can tell apart, e.g. most
+/// of them prove and recurse in a single SDK call, so only [`ProvePhase::Prove`]
+/// is reported for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, FromRepr)]
+pub enum ProvePhase {
+    /// Setting up prover or keys for the proving.
+    Setup,
+    /// Executing the program before proving, e.g. to enforce the cycle limit.
+    Execute,
+    /// Proving the program execution.
+    Prove,
+    /// Aggregating the proofs by recursion.
+    Recursion,
+    /// Wrapping the proof into a SNARK (e.g. Groth16).
+    Wrap,
+}
+
+/// Number of segments proved so far out of the total, reported along with
+/// [`ProvePhase::Prove`] by the zkVMs that prove the segments one by one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentProgress {
+    pub proved: u64,
+    pub total: u64,
+}
+
+/// Progress of an in-flight proving, reported by [`ProgressCallback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveProgress {
+    pub phase: ProvePhase,
+    /// `None` if the zkVM doesn't report the segments, or it's not proving
+    /// the segments.
+    pub segments: Option<SegmentProgress>,
+}
+
+impl From<ProvePhase> for ProveProgress {
+    fn from(phase: ProvePhase) -> Self {
+        Self {
+            phase,
+            segments: None,
+        }
+    }
+}
+
+impl fmt::Display for ProveProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.phase)?;
+        if let Some(SegmentProgress { proved, total }) = self.segments {
+            write!(f, " (segment {proved}/{total})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback to receive the [`ProveProgress`] changes, set by
+/// [`ProveOptions::with_progress`].
+///
+/// It's called in the proving thread, so it should return quickly.
+///
+/// [`ProveOptions::with_progress`]: crate::zkvm::ProveOptions::with_progress
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(ProveProgress) + Send + Sync>);
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(ProveProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub fn report(&self, progress: impl Into<ProveProgress>) {
+        (self.0)(progress.into())
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ProgressCallback").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::{ProveOptions, ProvePhase, ProveProgress};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_report_segments() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let options = ProveOptions::default().with_progress({
+            let reported = reported.clone();
+            move |progress: ProveProgress| reported.lock().unwrap().push(progress.to_string())
+        });
+
+        options.report_progress(ProvePhase::Execute);
+        options.report_segments(1, 3);
+        options.report_progress(ProvePhase::Recursion);
+        assert_eq!(
+            *reported.lock().unwrap(),
+            ["Execute", "Prove (segment 1/3)", "Recursion"]
+        );
+    }
+}
@@ -6,7 +6,7 @@ use strum::{EnumDiscriminants, EnumIs, EnumTryAs, FromRepr};
 /// Proof generated by [`zkVM::prove`], that also includes the [`PublicValues`]
 /// for [`zkVM::verify`] to work.
 #[derive(Clone, Debug, Serialize, Deserialize, EnumDiscriminants, EnumIs, EnumTryAs)]
-#[strum_discriminants(derive(Default, FromRepr))]
+#[strum_discriminants(derive(Default, FromRepr, Serialize, Deserialize))]
 #[strum_discriminants(name(ProofKind))]
 pub enum Proof {
     /// Compressed proof in contant size regardless of the cycle count.
@@ -14,6 +14,12 @@ pub enum Proof {
     Compressed(Vec<u8>),
     /// Groth16 proof that internally verifies a Compressed proof.
     Groth16(Vec<u8>),
+    /// Uncompressed proof whose size grows with the cycle count, which is
+    /// faster to produce (e.g. core proof of SP1 or composite receipt of
+    /// Risc0).
+    Core(Vec<u8>),
+    /// Plonk proof that internally verifies a Compressed proof.
+    Plonk(Vec<u8>),
 }
 
 impl Proof {
@@ -22,6 +28,8 @@ impl Proof {
         match proof_kind {
             ProofKind::Compressed => Self::Compressed(bytes),
             ProofKind::Groth16 => Self::Groth16(bytes),
+            ProofKind::Core => Self::Core(bytes),
+            ProofKind::Plonk => Self::Plonk(bytes),
         }
     }
 
@@ -35,6 +43,8 @@ impl Proof {
         match self {
             Self::Compressed(bytes) => bytes,
             Self::Groth16(bytes) => bytes,
+            Self::Core(bytes) => bytes,
+            Self::Plonk(bytes) => bytes,
         }
     }
 }
@@ -0,0 +1,73 @@
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+/// Prefix of the line written by guest program to stdout to start a region
+/// for cycle tracking, followed by the region name.
+///
+/// The convention follows SP1's `cycle-tracker-report-*`, whose executor
+/// counts the cycles natively. For other zkVMs the guest program appends the
+/// current cycle count as ` @ <cycles>`, e.g.
+/// `cycle-tracker-report-start: hash @ 1234`, which is then parsed from the
+/// guest output by [`region_cycles`].
+pub const REGION_START_MARKER: &str = "cycle-tracker-report-start:";
+
+/// Prefix of the line written by guest program to stdout to end a region for
+/// cycle tracking, see [`REGION_START_MARKER`] for the convention.
+pub const REGION_END_MARKER: &str = "cycle-tracker-report-end:";
+
+/// Parses the region markers in guest stdout, and returns cycles of each
+/// region in order of the region ends.
+///
+/// Cycles of the regions with the same name are summed up. Markers without
+/// cycle count and end markers without matching start are ignored.
+pub fn region_cycles(stdout: &str) -> IndexMap<String, u64> {
+    let mut starts = HashMap::<&str, Vec<u64>>::new();
+    let mut region_cycles = IndexMap::new();
+    for line in stdout.lines() {
+        if let Some((name, cycles)) = parse_marker(line, REGION_START_MARKER) {
+            starts.entry(name).or_default().push(cycles);
+        } else if let Some((name, cycles)) = parse_marker(line, REGION_END_MARKER) {
+            if let Some(start) = starts.get_mut(name).and_then(Vec::pop) {
+                *region_cycles.entry(name.to_string()).or_default() += cycles.saturating_sub(start);
+            }
+        }
+    }
+    region_cycles
+}
+
+fn parse_marker<'a>(line: &'a str, marker: &str) -> Option<(&'a str, u64)> {
+    let (name, cycles) = line.trim().strip_prefix(marker)?.rsplit_once(" @ ")?;
+    Some((name.trim(), cycles.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zkvm::region_cycles;
+
+    #[test]
+    fn test_region_cycles() {
+        let stdout = "\
+cycle-tracker-report-start: read_input @ 10
+cycle-tracker-report-end: read_input @ 30
+hello
+cycle-tracker-report-start: compute @ 40
+cycle-tracker-report-start: hash @ 50
+cycle-tracker-report-end: hash @ 70
+cycle-tracker-report-start: hash @ 80
+cycle-tracker-report-end: hash @ 85
+cycle-tracker-report-end: compute @ 100
+cycle-tracker-report-start: no_cycles
+cycle-tracker-report-end: no_cycles
+cycle-tracker-report-end: no_start @ 110
+";
+        let region_cycles = region_cycles(stdout);
+        assert_eq!(
+            region_cycles.into_iter().collect::<Vec<_>>(),
+            [
+                ("read_input".to_string(), 20),
+                ("hash".to_string(), 25),
+                ("compute".to_string(), 60),
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::zkvm::Proof;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -9,9 +10,17 @@ pub struct ProgramExecutionReport {
     /// Total number of cycles for the entire workload execution.
     pub total_num_cycles: u64,
     /// Region-specific cycles, mapping region names (e.g., "setup", "compute") to their cycle counts.
+    ///
+    /// Only filled by the zkVMs that expose the guest output or track the
+    /// regions natively, see [`region_cycles`] for the marker convention.
+    ///
+    /// [`region_cycles`]: crate::zkvm::region_cycles
     pub region_cycles: IndexMap<String, u64>,
     /// Execution duration.
     pub execution_duration: Duration,
+    /// Output written by the guest program, `None` if the zkVM doesn't support
+    /// capturing it.
+    pub guest_output: Option<GuestOutput>,
 }
 
 impl ProgramExecutionReport {
@@ -26,14 +35,74 @@ impl ProgramExecutionReport {
     }
 }
 
+/// Output written by the guest program to stdout and stderr.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuestOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl GuestOutput {
+    pub fn new(stdout: &[u8], stderr: &[u8]) -> Self {
+        Self {
+            stdout: String::from_utf8_lossy(stdout).to_string(),
+            stderr: String::from_utf8_lossy(stderr).to_string(),
+        }
+    }
+}
+
 /// ProgramProvingReport produces information about proving a particular
 /// program's instance.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProgramProvingReport {
+    /// Total proving duration, including execution, core proving, recursion
+    /// and wrapping, which are done in a single SDK call by all zkVMs.
     pub proving_time: Duration,
+    /// Duration of setup and key generation, which is not included in
+    /// [`ProgramProvingReport::proving_time`], `None` if the zkVM doesn't
+    /// expose it. For most zkVMs it's done once when the instance is created.
+    pub setup_time: Option<Duration>,
+    /// Size of the serialized proof in bytes.
+    pub proof_size: u64,
+    /// Peak resident memory in bytes of the proving process, `None` if the
+    /// proving is done in another process or it's unavailable on the platform.
+    ///
+    /// It's measured process-wide, see [`peak_memory`] for the caveat. So
+    /// `ere-server` drops it if other zkVM calls ran concurrently.
+    ///
+    /// [`peak_memory`]: crate::zkvm::peak_memory
+    pub peak_memory: Option<u64>,
+    /// Number of segments (or shards) the execution is split into, `None` if
+    /// the zkVM doesn't expose it.
+    pub num_segments: Option<u64>,
 }
+
 impl ProgramProvingReport {
     pub fn new(proving_time: Duration) -> Self {
-        Self { proving_time }
+        Self {
+            proving_time,
+            ..Default::default()
+        }
+    }
+
+    /// Sets [`ProgramProvingReport::proof_size`] to the size of `proof`.
+    pub fn with_proof_size(mut self, proof: &Proof) -> Self {
+        self.proof_size = proof.as_bytes().len() as u64;
+        self
+    }
+
+    pub fn with_setup_time(mut self, setup_time: Duration) -> Self {
+        self.setup_time = Some(setup_time);
+        self
+    }
+
+    pub fn with_peak_memory(mut self, peak_memory: Option<u64>) -> Self {
+        self.peak_memory = peak_memory;
+        self
+    }
+
+    pub fn with_num_segments(mut self, num_segments: u64) -> Self {
+        self.num_segments = Some(num_segments);
+        self
     }
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use strum::{EnumDiscriminants, EnumIs};
 
 /// Configuration for network-based proving
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -24,7 +25,9 @@ impl NetworkProverConfig {
 }
 
 /// ResourceType specifies what resource will be used to create the proofs.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, EnumDiscriminants, EnumIs)]
+#[strum_discriminants(derive(Serialize, Deserialize))]
+#[strum_discriminants(name(ProverResourceKind))]
 #[cfg_attr(feature = "clap", derive(clap::Subcommand))]
 pub enum ProverResourceType {
     #[default]
@@ -32,6 +35,17 @@ pub enum ProverResourceType {
     Gpu,
     /// Use a remote prover network
     Network(NetworkProverConfig),
+    /// Execute only and create fake proof, which is only accepted by zkVM
+    /// instance also created in mock mode. Useful for testing the wiring
+    /// without real proving.
+    Mock,
+}
+
+impl ProverResourceType {
+    /// Returns [`ProverResourceKind`].
+    pub fn kind(&self) -> ProverResourceKind {
+        ProverResourceKind::from(self)
+    }
 }
 
 #[cfg(feature = "clap")]
@@ -43,6 +57,7 @@ impl ProverResourceType {
             Self::Network(config) => core::iter::once("network")
                 .chain(config.to_args())
                 .collect(),
+            Self::Mock => vec!["mock"],
         }
     }
 }
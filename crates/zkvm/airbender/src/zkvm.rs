@@ -1,88 +1,96 @@
 use crate::{program::AirbenderProgram, zkvm::sdk::AirbenderSdk};
-use airbender_execution_utils::ProgramProof;
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, KeyCache, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    mock_prove, mock_verify, region_cycles, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
 };
 use std::time::Instant;
 
 mod error;
 mod sdk;
+mod verifier;
 
 pub use error::Error;
 pub use sdk::VkHashChain;
+pub use verifier::EreAirbenderVerifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
 pub struct EreAirbender {
     sdk: AirbenderSdk,
+    resource: ProverResourceType,
 }
 
 impl EreAirbender {
     pub fn new(program: AirbenderProgram, resource: ProverResourceType) -> Result<Self, Error> {
+        Self::check_prover_resource(&resource)?;
         let gpu = matches!(resource, ProverResourceType::Gpu);
-        let sdk = AirbenderSdk::new(program.bin(), gpu);
-        Ok(Self { sdk })
+        let sdk = AirbenderSdk::new(program.bin(), gpu, &KeyCache::from_env())?;
+        Ok(Self { sdk, resource })
     }
 }
 
 impl zkVM for EreAirbender {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let start = Instant::now();
-        let (public_values, cycles) = self.sdk.execute(input)?;
+        let (public_values, cycles, guest_output) = self.sdk.execute(input, options.cycle_limit)?;
         let execution_duration = start.elapsed();
 
         Ok((
             public_values,
             ProgramExecutionReport {
                 total_num_cycles: cycles,
+                region_cycles: region_cycles(&guest_output.stdout),
                 execution_duration,
-                ..Default::default()
+                guest_output: Some(guest_output),
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
+        }
+
+        let cancellation = options.cancellation();
+
+        // Execute first to enforce the cycle limit, since `airbender-cli prove`
+        // doesn't tell whether the limit is exceeded.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
         }
+
         let start = Instant::now();
-        let (public_values, proof) = self.sdk.prove(input)?;
+        let (public_values, proof) = self.sdk.prove(input, options, &cancellation)?;
         let proving_time = start.elapsed();
 
         let proof_bytes = bincode::serde::encode_to_vec(&proof, bincode::config::legacy())
             .map_err(|err| CommonError::serialize("proof", "bincode", err))?;
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(proving_time),
-        ))
-    }
-
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
-        };
+        let proof = Proof::Compressed(proof_bytes);
+        let report = ProgramProvingReport::new(proving_time).with_proof_size(&proof);
 
-        let (proof, _): (ProgramProof, _) =
-            bincode::serde::decode_from_slice(proof, bincode::config::legacy())
-                .map_err(|err| CommonError::deserialize("proof", "bincode", err))?;
+        Ok((public_values, proof, report))
+    }
 
-        let public_values = self.sdk.verify(&proof)?;
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
 
-        Ok(public_values)
+        verifier::verify(self.sdk.vk_chain_hash(), proof)
     }
 
     fn name(&self) -> &'static str {
@@ -94,24 +102,41 @@ impl zkVM for EreAirbender {
     }
 }
 
+impl zkVMCapabilities for EreAirbender {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] = &[
+        ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu,
+        ProverResourceKind::Mock,
+    ];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
 impl zkVMProgramDigest for EreAirbender {
     type ProgramDigest = VkHashChain;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(*self.sdk.vk_chain_hash())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{compiler::RustRv32ima, program::AirbenderProgram, zkvm::EreAirbender};
+    use crate::{
+        compiler::RustRv32ima,
+        program::AirbenderProgram,
+        zkvm::{EreAirbender, EreAirbenderVerifier},
+    };
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, run_zkvm_prove_with_verifier, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{ProofKind, ProverResourceType, zkVM, zkVMProgramDigest},
     };
     use std::sync::OnceLock;
 
@@ -145,6 +170,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreAirbender::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid().into_output_sha256();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -154,6 +188,16 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreAirbender::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreAirbender::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
@@ -163,4 +207,14 @@ mod tests {
             zkvm.prove(&input, ProofKind::default()).unwrap_err();
         }
     }
+
+    #[test]
+    fn test_prove_with_verifier() {
+        let program = basic_program();
+        let zkvm = EreAirbender::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier = EreAirbenderVerifier::new(zkvm.program_digest().unwrap());
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_verifier(&zkvm, &verifier, &test_case);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::zkvm::sdk::VkHashChain;
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,6 +8,9 @@ pub enum Error {
     CommonError(#[from] CommonError),
 
     // Execution
+    #[error("Airbender execution failed: {0}")]
+    Execute(#[source] CommonError),
+
     #[error("Failed to parse public value from stdout: {0}")]
     ParsePublicValue(String),
 
@@ -29,3 +32,19 @@ pub enum Error {
         proved: VkHashChain,
     },
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(ref inner) => {
+                let exit_code = inner.exit_code();
+                zkVMError::guest_panic(err, exit_code)
+            }
+            Error::ProofVerificationFailed
+            | Error::InvalidRegisterCount(_)
+            | Error::UnexpectedVkHashChain { .. } => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
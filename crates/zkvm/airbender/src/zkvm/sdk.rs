@@ -1,9 +1,11 @@
-use crate::zkvm::error::Error;
+use crate::zkvm::{NAME, SDK_VERSION, error::Error};
 use airbender_execution_utils::{
     Machine, ProgramProof, compute_chain_encoding, generate_params_for_binary,
     universal_circuit_verifier_vk, verify_recursion_log_23_layer,
 };
-use ere_zkvm_interface::zkvm::{CommonError, PublicValues};
+use ere_zkvm_interface::zkvm::{
+    Cancellation, CommonError, GuestOutput, KeyCache, ProveOptions, ProvePhase, PublicValues,
+};
 use std::{array, fs, io::BufRead, iter, process::Command};
 use tempfile::tempdir;
 
@@ -25,28 +27,40 @@ pub struct AirbenderSdk {
 }
 
 impl AirbenderSdk {
-    pub fn new(bin: &[u8], gpu: bool) -> Self {
-        let vk_hash_chain = {
-            // Compute base VK as `blake(PC || setup_caps)`.
-            let base_vk = generate_params_for_binary(bin, Machine::Standard);
-            // The 1st recursion layer VK
-            let verifier_vk = universal_circuit_verifier_vk().params;
-            // Compute hash chain as `blake(blake(0 || guest_vk) || verifier_vk)`,
-            // that is expected to be exposed by second layer recursion program.
-            compute_chain_encoding(vec![[0; 8], base_vk, verifier_vk])
-        };
-        Self {
+    /// Returns SDK for the binary, the VK hash chain is loaded from
+    /// [`KeyCache`] if it's enabled and has been computed before.
+    pub fn new(bin: &[u8], gpu: bool, cache: &KeyCache) -> Result<Self, Error> {
+        let vk_hash_chain =
+            cache.get_or_insert_with(NAME, SDK_VERSION, bin, "vk_hash_chain", || {
+                // Compute base VK as `blake(PC || setup_caps)`.
+                let base_vk = generate_params_for_binary(bin, Machine::Standard);
+                // The 1st recursion layer VK
+                let verifier_vk = universal_circuit_verifier_vk().params;
+                // Compute hash chain as `blake(blake(0 || guest_vk) || verifier_vk)`,
+                // that is expected to be exposed by second layer recursion program.
+                Ok::<_, Error>(compute_chain_encoding(vec![[0; 8], base_vk, verifier_vk]))
+            })?;
+        Ok(Self {
             bin: bin.to_vec(),
             vk_hash_chain,
             gpu,
-        }
+        })
     }
 
     pub fn vk_chain_hash(&self) -> &VkHashChain {
         &self.vk_hash_chain
     }
 
-    pub fn execute(&self, input: &[u8]) -> Result<(PublicValues, u64), Error> {
+    /// Executes the program with `cycle_limit`, returns
+    /// [`CommonError::CycleLimitExceeded`] if the program doesn't finish in it.
+    ///
+    /// Returns the public values, total cycles and the `airbender-cli` output,
+    /// which contains the guest output.
+    pub fn execute(
+        &self,
+        input: &[u8],
+        cycle_limit: Option<u64>,
+    ) -> Result<(PublicValues, u64, GuestOutput), Error> {
         let tempdir = tempdir().map_err(CommonError::tempdir)?;
 
         let bin_path = tempdir.path().join("guest.bin");
@@ -64,16 +78,43 @@ impl AirbenderSdk {
             .arg(&bin_path)
             .arg("--input-file")
             .arg(&input_path)
-            .args(["--cycles", &u64::MAX.to_string()])
+            // Allow 1 more cycle so we can tell whether the limit is exceeded.
+            .args([
+                "--cycles",
+                &cycle_limit
+                    .map_or(u64::MAX, |limit| limit.saturating_add(1))
+                    .to_string(),
+            ])
             .output()
             .map_err(|err| CommonError::command(&cmd, err))?;
 
         if !output.status.success() {
-            Err(CommonError::command_exit_non_zero(
+            Err(Error::Execute(CommonError::command_exit_non_zero(
                 &cmd,
                 output.status,
                 Some(&output),
-            ))?
+            )))?
+        }
+
+        // Parse cycles from stdout in format of:
+        // `Took {cycles} cycles to finish`
+        let cycles = output
+            .stdout
+            .lines()
+            .find_map(|line| {
+                let line = line.ok()?;
+                let line = line.split_once("Took ")?.1;
+                let cycle = line.split_once(" cycles")?.0;
+                cycle.parse().ok()
+            })
+            .ok_or_else(|| {
+                Error::ParseCycles(String::from_utf8_lossy(&output.stdout).to_string())
+            })?;
+
+        if let Some(cycle_limit) = cycle_limit
+            && cycles > cycle_limit
+        {
+            return Err(CommonError::CycleLimitExceeded { cycle_limit }.into());
         }
 
         // Parse public values 8 u32 words (32 bytes) from stdout in format of:
@@ -95,25 +136,18 @@ impl AirbenderSdk {
                 Error::ParsePublicValue(String::from_utf8_lossy(&output.stdout).to_string())
             })?;
 
-        // Parse cycles from stdout in format of:
-        // `Took {cycles} cycles to finish`
-        let cycles = output
-            .stdout
-            .lines()
-            .find_map(|line| {
-                let line = line.ok()?;
-                let line = line.split_once("Took ")?.1;
-                let cycle = line.split_once(" cycles")?.0;
-                cycle.parse().ok()
-            })
-            .ok_or_else(|| {
-                Error::ParseCycles(String::from_utf8_lossy(&output.stdout).to_string())
-            })?;
+        let guest_output = GuestOutput::new(&output.stdout, &output.stderr);
 
-        Ok((public_values, cycles))
+        Ok((public_values, cycles, guest_output))
     }
 
-    pub fn prove(&self, input: &[u8]) -> Result<(PublicValues, ProgramProof), Error> {
+    /// Proves by `airbender-cli`, which is killed if the proving is cancelled.
+    pub fn prove(
+        &self,
+        input: &[u8],
+        options: &ProveOptions,
+        cancellation: &Cancellation,
+    ) -> Result<(PublicValues, ProgramProof), Error> {
         let tempdir = tempdir().map_err(CommonError::tempdir)?;
 
         let bin_path = tempdir.path().join("guest.bin");
@@ -129,9 +163,9 @@ impl AirbenderSdk {
             .map_err(|err| CommonError::create_dir("output", &output_dir, err))?;
 
         // Prove guest program + 1st recursion layer (tree of recursive proofs until root).
+        options.report_progress(ProvePhase::Prove);
         let mut cmd = Command::new("airbender-cli");
-        let output = cmd
-            .arg("prove")
+        cmd.arg("prove")
             .arg("--bin")
             .arg(&bin_path)
             .arg("--output-dir")
@@ -140,9 +174,8 @@ impl AirbenderSdk {
             .arg(&input_path)
             .args(["--until", "final-recursion"])
             .args(["--cycles", &u64::MAX.to_string()])
-            .args(self.gpu.then_some("--gpu"))
-            .output()
-            .map_err(|err| CommonError::command(&cmd, err))?;
+            .args(self.gpu.then_some("--gpu"));
+        let output = cancellation.output(&mut cmd)?;
 
         if !output.status.success() {
             Err(CommonError::command_exit_non_zero(
@@ -158,16 +191,15 @@ impl AirbenderSdk {
         }
 
         // Prove 2nd recursion layer (wrapping root of 1st recursion layer)
+        options.report_progress(ProvePhase::Recursion);
         let mut cmd = Command::new("airbender-cli");
-        let output = cmd
-            .arg("prove-final")
+        cmd.arg("prove-final")
             .arg("--input-file")
             .arg(&proof_path)
             .arg("--output-dir")
             .arg(&output_dir)
-            .args(self.gpu.then_some("--gpu"))
-            .output()
-            .map_err(|err| CommonError::command(&cmd, err))?;
+            .args(self.gpu.then_some("--gpu"));
+        let output = cancellation.output(&mut cmd)?;
 
         if !output.status.success() {
             Err(CommonError::command_exit_non_zero(
@@ -195,24 +227,25 @@ impl AirbenderSdk {
 
         Ok((public_values, proof))
     }
+}
 
-    pub fn verify(&self, proof: &ProgramProof) -> Result<PublicValues, Error> {
-        let is_valid = verify_recursion_log_23_layer(proof);
-        if !is_valid {
-            return Err(Error::ProofVerificationFailed);
-        }
+/// Verifies the proof and checks it's proved for the program of `vk_hash_chain`.
+pub fn verify(vk_hash_chain: &VkHashChain, proof: &ProgramProof) -> Result<PublicValues, Error> {
+    let is_valid = verify_recursion_log_23_layer(proof);
+    if !is_valid {
+        return Err(Error::ProofVerificationFailed);
+    }
 
-        let (public_values, vk_hash_chain) = extract_public_values_and_vk_hash_chain(proof)?;
+    let (public_values, proved_vk_hash_chain) = extract_public_values_and_vk_hash_chain(proof)?;
 
-        if self.vk_hash_chain != vk_hash_chain {
-            return Err(Error::UnexpectedVkHashChain {
-                preprocessed: self.vk_hash_chain,
-                proved: vk_hash_chain,
-            });
-        }
-
-        Ok(public_values)
+    if *vk_hash_chain != proved_vk_hash_chain {
+        return Err(Error::UnexpectedVkHashChain {
+            preprocessed: *vk_hash_chain,
+            proved: proved_vk_hash_chain,
+        });
     }
+
+    Ok(public_values)
 }
 
 /// Encode input with length prefixed to hex string for `airbender-cli`.
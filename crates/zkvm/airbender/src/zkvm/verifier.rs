@@ -0,0 +1,55 @@
+use crate::zkvm::{NAME, SDK_VERSION, sdk::VkHashChain};
+use airbender_execution_utils::ProgramProof;
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+
+/// Verifier of Airbender proofs created from [`VkHashChain`] only.
+pub struct EreAirbenderVerifier {
+    vk_hash_chain: VkHashChain,
+}
+
+impl EreAirbenderVerifier {
+    pub fn new(vk_hash_chain: VkHashChain) -> Self {
+        Self { vk_hash_chain }
+    }
+}
+
+impl zkVMVerifier for EreAirbenderVerifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(&self.vk_hash_chain, proof)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreAirbenderVerifier {
+    type ProgramDigest = VkHashChain;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.vk_hash_chain)
+    }
+}
+
+pub fn verify(vk_hash_chain: &VkHashChain, proof: &Proof) -> Result<PublicValues, zkVMError> {
+    let Proof::Compressed(proof) = proof else {
+        return Err(
+            CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+        );
+    };
+
+    let (proof, _): (ProgramProof, _) =
+        bincode::serde::decode_from_slice(proof, bincode::config::legacy()).map_err(|err| {
+            zkVMError::invalid_proof(CommonError::deserialize("proof", "bincode", err))
+        })?;
+
+    let public_values = crate::zkvm::sdk::verify(vk_hash_chain, &proof)?;
+
+    Ok(public_values)
+}
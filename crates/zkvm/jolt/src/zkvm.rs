@@ -2,13 +2,19 @@ use crate::{
     program::JoltProgram,
     zkvm::sdk::{JoltProof, JoltSdk},
 };
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM,
+    CommonError, ExecuteOptions, KeyCache, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    mock_prove, mock_verify, peak_memory, reset_peak_memory, zkVM, zkVMCapabilities, zkVMError,
+    zkVMProgramDigest,
 };
 use jolt_ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use std::{env, io::Cursor, time::Instant};
+use sha2::{Digest, Sha256};
+use std::{
+    env,
+    io::Cursor,
+    time::{Duration, Instant},
+};
 
 mod error;
 mod sdk;
@@ -19,26 +25,43 @@ include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
 pub struct EreJolt {
     sdk: JoltSdk,
+    resource: ProverResourceType,
+    /// Time spent on preprocessing
+    setup_time: Duration,
 }
 
 impl EreJolt {
     pub fn new(program: JoltProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if !matches!(resource, ProverResourceType::Cpu) {
-            panic!("Network or GPU proving not yet implemented for Miden. Use CPU resource type.");
-        }
-        let sdk = JoltSdk::new(program.elf());
-        Ok(EreJolt { sdk })
+        Self::check_prover_resource(&resource)?;
+        let start = Instant::now();
+        let sdk = JoltSdk::new(program.elf(), &KeyCache::from_env())?;
+        let setup_time = start.elapsed();
+        Ok(EreJolt {
+            sdk,
+            resource,
+            setup_time,
+        })
     }
 }
 
 impl zkVM for EreJolt {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let start = Instant::now();
         let (public_values, total_num_cycles) = self.sdk.execute(input)?;
         let execution_duration = start.elapsed();
 
+        // The SDK can't stop the execution at a limit, so the limit is only
+        // checked after execution, and a guest that never halts isn't stopped.
+        options.check_cycles(total_num_cycles)?;
+
         Ok((
             public_values,
+            // Region cycles are not reported, the executor prints the guest
+            // output to the process stdout instead of returning it.
             ProgramExecutionReport {
                 total_num_cycles,
                 execution_duration,
@@ -47,17 +70,27 @@ impl zkVM for EreJolt {
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
         }
+
+        // Execute first to check the cycle limit, since the prover doesn't
+        // support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
+        }
+
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
         let start = Instant::now();
         let (public_values, proof) = self.sdk.prove(input)?;
         let proving_time = start.elapsed();
@@ -67,23 +100,29 @@ impl zkVM for EreJolt {
             .serialize_compressed(&mut proof_bytes)
             .map_err(|err| CommonError::serialize("proof", "jolt", err))?;
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(proving_time),
-        ))
+        let proof = Proof::Compressed(proof_bytes);
+        let report = ProgramProvingReport::new(proving_time)
+            .with_setup_time(self.setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
+
+        Ok((public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
+
         let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
+            return Err(
+                CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+            );
         };
 
-        let proof = JoltProof::deserialize_compressed(&mut Cursor::new(proof))
-            .map_err(|err| CommonError::deserialize("proof", "jolt", err))?;
+        let proof = JoltProof::deserialize_compressed(&mut Cursor::new(proof)).map_err(|err| {
+            zkVMError::invalid_proof(CommonError::deserialize("proof", "jolt", err))
+        })?;
 
         let public_values = self.sdk.verify(proof)?;
 
@@ -99,11 +138,30 @@ impl zkVM for EreJolt {
     }
 }
 
+impl zkVMCapabilities for EreJolt {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
+impl zkVMProgramDigest for EreJolt {
+    /// SHA-256 digest of the ELF, which the proof is verified against.
+    type ProgramDigest = [u8; 32];
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(Sha256::digest(self.sdk.elf()).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{compiler::RustRv64imacCustomized, program::JoltProgram, zkvm::EreJolt};
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
@@ -147,6 +205,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreJolt::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -158,6 +225,16 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreJolt::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreJolt::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
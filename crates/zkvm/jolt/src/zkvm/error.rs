@@ -1,4 +1,4 @@
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use jolt_core::utils::errors::ProofVerifyError;
 use thiserror::Error;
 
@@ -15,3 +15,13 @@ pub enum Error {
     #[error("Failed to verify proof: {0}")]
     VerifyProofFailed(#[from] ProofVerifyError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::ExecutionPanic => zkVMError::guest_panic(err, None),
+            Error::VerifyProofFailed(_) => zkVMError::invalid_proof(err),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use crate::zkvm::Error;
-use ere_zkvm_interface::zkvm::{CommonError, PublicValues};
+use crate::zkvm::{Error, NAME, SDK_VERSION};
+use ere_zkvm_interface::zkvm::{CommonError, KeyCache, PublicValues};
 use jolt_ark_serialize::{self as ark_serialize, CanonicalDeserialize, CanonicalSerialize};
 use jolt_common::constants::{
     DEFAULT_MAX_INPUT_SIZE, DEFAULT_MAX_OUTPUT_SIZE, DEFAULT_MAX_TRACE_LENGTH, DEFAULT_MEMORY_SIZE,
@@ -15,6 +15,7 @@ use jolt_sdk::{
     guest::program::{decode, trace},
     postcard,
 };
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
 
 #[derive(CanonicalSerialize, CanonicalDeserialize)]
 pub struct JoltProof {
@@ -25,6 +26,30 @@ pub struct JoltProof {
     outputs: Vec<u8>,
 }
 
+/// [`JoltProverPreprocessing`] (de)serialized by `ark_serialize`, to be
+/// stored in [`KeyCache`].
+struct CachedPreprocessing(JoltProverPreprocessing<F, PCS>);
+
+impl Serialize for CachedPreprocessing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::new();
+        self.0
+            .serialize_uncompressed(&mut bytes)
+            .map_err(ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for CachedPreprocessing {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        // Skip the validation since the bytes are serialized by ourselves.
+        JoltProverPreprocessing::deserialize_uncompressed_unchecked(bytes.as_slice())
+            .map(Self)
+            .map_err(de::Error::custom)
+    }
+}
+
 pub struct JoltSdk {
     elf: Vec<u8>,
     memory_config: MemoryConfig,
@@ -33,7 +58,9 @@ pub struct JoltSdk {
 }
 
 impl JoltSdk {
-    pub fn new(elf: &[u8]) -> Self {
+    /// Returns SDK for the ELF, the preprocessing is loaded from [`KeyCache`]
+    /// if it's enabled and has been done before.
+    pub fn new(elf: &[u8], cache: &KeyCache) -> Result<Self, Error> {
         let (bytecode, memory_init, program_size) = decode(elf);
         let memory_config = MemoryConfig {
             max_input_size: DEFAULT_MAX_INPUT_SIZE,
@@ -44,22 +71,31 @@ impl JoltSdk {
         };
         let memory_layout = MemoryLayout::new(&memory_config);
         let max_trace_length = DEFAULT_MAX_TRACE_LENGTH as usize;
-        let pk = {
-            // FIXME: Use public trusted setup or switch to other transparent PCS.
-            let max_trace_length = max_trace_length.next_power_of_two();
-            let generators = PCS::setup_prover(DTH_ROOT_OF_K.log_2() + max_trace_length.log_2());
+        let CachedPreprocessing(pk) =
+            cache.get_or_insert_with(NAME, SDK_VERSION, elf, "pk", || {
+                // FIXME: Use public trusted setup or switch to other transparent PCS.
+                let max_trace_length = max_trace_length.next_power_of_two();
+                let generators =
+                    PCS::setup_prover(DTH_ROOT_OF_K.log_2() + max_trace_length.log_2());
 
-            let shared = JoltRV64IMAC::shared_preprocess(bytecode, memory_layout, memory_init);
+                let shared = JoltRV64IMAC::shared_preprocess(bytecode, memory_layout, memory_init);
 
-            JoltProverPreprocessing { generators, shared }
-        };
+                Ok::<_, Error>(CachedPreprocessing(JoltProverPreprocessing {
+                    generators,
+                    shared,
+                }))
+            })?;
         let vk = JoltVerifierPreprocessing::from(&pk);
-        Self {
+        Ok(Self {
             elf: elf.to_vec(),
             memory_config,
             pk,
             vk,
-        }
+        })
+    }
+
+    pub fn elf(&self) -> &[u8] {
+        &self.elf
     }
 
     pub fn execute(&self, input: &[u8]) -> Result<(PublicValues, u64), Error> {
@@ -1,25 +1,24 @@
 use crate::program::{MidenProgram, MidenProgramInfo, MidenSerdeWrapper};
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
-};
-use miden_core::{
-    Program,
-    utils::{Deserializable, Serializable},
+    CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
+    ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues, mock_prove,
+    mock_verify, peak_memory, reset_peak_memory, zkVM, zkVMCapabilities, zkVMError,
+    zkVMProgramDigest,
 };
+use miden_core::{Program, utils::Serializable};
 use miden_processor::{
-    DefaultHost, ExecutionOptions, ProgramInfo, StackInputs, StackOutputs, execute as miden_execute,
+    DefaultHost, ExecutionError, ExecutionOptions, StackInputs, execute as miden_execute,
 };
-use miden_prover::{AdviceInputs, ExecutionProof, ProvingOptions, prove as miden_prove};
+use miden_prover::{AdviceInputs, ProvingOptions, prove as miden_prove};
 use miden_stdlib::StdLibrary;
-use miden_verifier::verify as miden_verify;
 use std::{env, time::Instant};
 
 mod error;
+mod verifier;
 
 pub use error::Error;
 pub use miden_core::{Felt, FieldElement};
+pub use verifier::EreMidenVerifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
@@ -33,14 +32,16 @@ include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 /// [`felts_to_bytes`] as well.
 pub struct EreMiden {
     program: Program,
+    resource: ProverResourceType,
 }
 
 impl EreMiden {
     pub fn new(program: MidenProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if !matches!(resource, ProverResourceType::Cpu) {
-            panic!("Network or GPU proving not yet implemented for Miden. Use CPU resource type.");
-        }
-        Ok(Self { program: program.0 })
+        Self::check_prover_resource(&resource)?;
+        Ok(Self {
+            program: program.0,
+            resource,
+        })
     }
 
     fn setup_host() -> Result<DefaultHost, Error> {
@@ -51,13 +52,58 @@ impl EreMiden {
 
         Ok(host)
     }
+
+    /// Returns [`ExecutionOptions`] that stop the execution once it exceeds
+    /// the `cycle_limit`.
+    ///
+    /// Miden rounds the `max_cycles` up to power of two, so the caller still
+    /// needs to check the exact limit with the executed cycles.
+    fn execution_options(cycle_limit: Option<u64>) -> Result<ExecutionOptions, Error> {
+        let Some(cycle_limit) = cycle_limit else {
+            return Ok(ExecutionOptions::default());
+        };
+        let max_cycles = u32::try_from(cycle_limit)
+            .unwrap_or(u32::MAX)
+            .clamp(MIN_MAX_CYCLES, ExecutionOptions::MAX_CYCLES);
+        ExecutionOptions::new(
+            Some(max_cycles),
+            ExecutionOptions::default().expected_cycles(),
+            false,
+            false,
+        )
+        .map_err(Error::ExecutionOptions)
+    }
+}
+
+/// Minimum `max_cycles` accepted by [`ExecutionOptions::new`], which is the
+/// minimum trace length.
+const MIN_MAX_CYCLES: u32 = 64;
+
+/// Maps [`ExecutionError::CycleLimitExceeded`] into
+/// [`CommonError::CycleLimitExceeded`] with the requested `cycle_limit`.
+fn map_cycle_limit_exceeded(
+    cycle_limit: Option<u64>,
+    wrap: fn(ExecutionError) -> Error,
+) -> impl FnOnce(ExecutionError) -> Error {
+    move |err| match (err, cycle_limit) {
+        (ExecutionError::CycleLimitExceeded(_), Some(cycle_limit)) => {
+            CommonError::CycleLimitExceeded { cycle_limit }.into()
+        }
+        (err, _) => wrap(err),
+    }
 }
 
 impl zkVM for EreMiden {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let stack_inputs = StackInputs::default();
-        let advice_inputs = AdviceInputs::default().with_stack(bytes_to_felts(input)?);
+        let advice_inputs = AdviceInputs::default()
+            .with_stack(bytes_to_felts(input).map_err(zkVMError::invalid_input)?);
         let mut host = Self::setup_host()?;
+        let execution_options = Self::execution_options(options.cycle_limit)?;
 
         let start = Instant::now();
         let trace = miden_execute(
@@ -65,9 +111,12 @@ impl zkVM for EreMiden {
             stack_inputs,
             advice_inputs,
             &mut host,
-            ExecutionOptions::default(),
+            execution_options,
         )
-        .map_err(Error::Execute)?;
+        .map_err(map_cycle_limit_exceeded(
+            options.cycle_limit,
+            Error::Execute,
+        ))?;
 
         let public_values = felts_to_bytes(trace.stack_outputs().as_slice());
 
@@ -77,28 +126,41 @@ impl zkVM for EreMiden {
             ..Default::default()
         };
 
+        options.check_cycles(report.total_num_cycles)?;
+
         Ok((public_values, report))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
+        }
+
+        // Execute first to check the exact cycle limit, since the `max_cycles`
+        // of `ExecutionOptions` is rounded up to power of two.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
         }
 
         let stack_inputs = StackInputs::default();
-        let advice_inputs = AdviceInputs::default().with_stack(bytes_to_felts(input)?);
+        let advice_inputs = AdviceInputs::default()
+            .with_stack(bytes_to_felts(input).map_err(zkVMError::invalid_input)?);
         let mut host = Self::setup_host()?;
 
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
         let start = Instant::now();
         let proving_options =
-            ProvingOptions::with_96_bit_security(env::var_os("MIDEN_DEBUG").is_some());
+            ProvingOptions::with_96_bit_security(env::var_os("MIDEN_DEBUG").is_some())
+                .with_execution_options(Self::execution_options(options.cycle_limit)?);
 
         let (stack_outputs, proof) = miden_prove(
             &self.program,
@@ -107,37 +169,25 @@ impl zkVM for EreMiden {
             &mut host,
             proving_options,
         )
-        .map_err(Error::Prove)?;
+        .map_err(map_cycle_limit_exceeded(options.cycle_limit, Error::Prove))?;
+
+        let proving_time = start.elapsed();
 
         let public_values = felts_to_bytes(stack_outputs.as_slice());
-        let proof_bytes = (stack_outputs, proof).to_bytes();
+        let proof = Proof::Compressed((stack_outputs, proof).to_bytes());
+        let report = ProgramProvingReport::new(proving_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(start.elapsed()),
-        ))
+        Ok((public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
-        };
-
-        let program_info: ProgramInfo = self.program.clone().into();
-
-        let stack_inputs = StackInputs::default();
-        let (stack_outputs, proof): (StackOutputs, ExecutionProof) =
-            Deserializable::read_from_bytes(proof)
-                .map_err(|err| CommonError::deserialize("proof", "miden", err))?;
-
-        miden_verify(program_info, stack_inputs, stack_outputs.clone(), proof)
-            .map_err(Error::Verify)?;
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
 
-        Ok(felts_to_bytes(stack_outputs.as_slice()))
+        verifier::verify(self.program.clone().into(), proof)
     }
 
     fn name(&self) -> &'static str {
@@ -149,10 +199,17 @@ impl zkVM for EreMiden {
     }
 }
 
+impl zkVMCapabilities for EreMiden {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
 impl zkVMProgramDigest for EreMiden {
     type ProgramDigest = MidenProgramInfo;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(MidenSerdeWrapper(self.program.clone().into()))
     }
 }
@@ -186,12 +243,12 @@ mod tests {
     use crate::{
         compiler::MidenAsm,
         program::MidenProgram,
-        zkvm::{EreMiden, Felt, FieldElement, bytes_to_felts, felts_to_bytes},
+        zkvm::{EreMiden, EreMidenVerifier, Felt, FieldElement, bytes_to_felts, felts_to_bytes},
     };
     use ere_test_utils::host::testing_guest_directory;
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{ProofKind, ProverResourceType, zkVM, zkVMProgramDigest, zkVMVerifier},
     };
 
     fn load_miden_program(guest_name: &str) -> MidenProgram {
@@ -245,6 +302,50 @@ mod tests {
         assert_eq!(output[0], expected_fib);
     }
 
+    #[test]
+    fn test_prove_and_verify_with_verifier() {
+        let program = load_miden_program("add");
+        let zkvm = EreMiden::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier = EreMidenVerifier::new(zkvm.program_digest().unwrap());
+
+        let input = felts_to_bytes(&[Felt::ONE, Felt::ONE]);
+
+        // Prove
+        let (prover_public_values, proof, _) = zkvm.prove(&input, ProofKind::default()).unwrap();
+
+        // Verify
+        let verifier_public_values = verifier.verify(&proof).unwrap();
+        assert_eq!(prover_public_values, verifier_public_values);
+    }
+
+    #[test]
+    fn test_prove_mock() {
+        let program = load_miden_program("add");
+        let mock_zkvm = EreMiden::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreMiden::new(program, ProverResourceType::Cpu).unwrap();
+
+        let input = felts_to_bytes(&[Felt::ONE, Felt::ONE]);
+
+        // Prove
+        let (prover_public_values, proof, _) =
+            mock_zkvm.prove(&input, ProofKind::default()).unwrap();
+
+        // Verify
+        let verifier_public_values = mock_zkvm.verify(&proof).unwrap();
+        assert_eq!(prover_public_values, verifier_public_values);
+        zkvm.verify(&proof).unwrap_err();
+
+        // Assert output
+        let output = bytes_to_felts(&verifier_public_values).unwrap();
+        assert_eq!(output[0], Felt::ONE + Felt::ONE);
+    }
+
+    #[test]
+    fn test_unsupported_prover_resource() {
+        let program = load_miden_program("add");
+        assert!(EreMiden::new(program, ProverResourceType::Gpu).is_err());
+    }
+
     #[test]
     fn test_invalid_input() {
         let program = load_miden_program("add");
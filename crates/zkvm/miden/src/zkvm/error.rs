@@ -1,5 +1,5 @@
-use ere_zkvm_interface::zkvm::CommonError;
-use miden_processor::ExecutionError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
+use miden_processor::{ExecutionError, ExecutionOptionsError};
 use miden_verifier::VerificationError;
 use thiserror::Error;
 
@@ -9,6 +9,9 @@ pub enum Error {
     CommonError(#[from] CommonError),
 
     // Execute
+    #[error("Invalid Miden execution options: {0}")]
+    ExecutionOptions(#[source] ExecutionOptionsError),
+
     #[error("Miden execution failed")]
     Execute(#[from] ExecutionError),
 
@@ -20,3 +23,14 @@ pub enum Error {
     #[error("Miden verification failed")]
     Verify(#[from] VerificationError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::ExecutionOptions(_) | Error::Prove(_) => zkVMError::infrastructure(err),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::Verify(_) => zkVMError::invalid_proof(err),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    program::MidenProgramInfo,
+    zkvm::{Error, NAME, SDK_VERSION, felts_to_bytes},
+};
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+use miden_core::utils::Deserializable;
+use miden_processor::{ProgramInfo, StackInputs, StackOutputs};
+use miden_prover::ExecutionProof;
+use miden_verifier::verify as miden_verify;
+
+/// Verifier of Miden proofs created from [`MidenProgramInfo`] only.
+pub struct EreMidenVerifier {
+    program_info: MidenProgramInfo,
+}
+
+impl EreMidenVerifier {
+    pub fn new(program_info: MidenProgramInfo) -> Self {
+        Self { program_info }
+    }
+}
+
+impl zkVMVerifier for EreMidenVerifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(self.program_info.0.clone(), proof)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreMidenVerifier {
+    type ProgramDigest = MidenProgramInfo;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.program_info.clone())
+    }
+}
+
+pub fn verify(program_info: ProgramInfo, proof: &Proof) -> Result<PublicValues, zkVMError> {
+    let Proof::Compressed(proof) = proof else {
+        return Err(
+            CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+        );
+    };
+
+    let stack_inputs = StackInputs::default();
+    let (stack_outputs, proof): (StackOutputs, ExecutionProof) =
+        Deserializable::read_from_bytes(proof).map_err(|err| {
+            zkVMError::invalid_proof(CommonError::deserialize("proof", "miden", err))
+        })?;
+
+    miden_verify(program_info, stack_inputs, stack_outputs.clone(), proof)
+        .map_err(Error::Verify)?;
+
+    Ok(felts_to_bytes(stack_outputs.as_slice()))
+}
@@ -1,8 +1,9 @@
 use crate::program::NexusProgram;
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM,
+    CommonError, ExecuteOptions, GuestOutput, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    mock_prove, mock_verify, peak_memory, region_cycles, reset_peak_memory, zkVM, zkVMCapabilities,
+    zkVMError, zkVMProgramDigest,
 };
 use nexus_core::nvm::{self, ElfFile};
 use nexus_sdk::{
@@ -11,6 +12,7 @@ use nexus_sdk::{
 };
 use nexus_vm::trace::Trace;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::time::Instant;
 use tracing::info;
 
@@ -28,19 +30,22 @@ pub struct NexusProofBundle {
 
 pub struct EreNexus {
     program: NexusProgram,
+    resource: ProverResourceType,
 }
 
 impl EreNexus {
     pub fn new(program: NexusProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if !matches!(resource, ProverResourceType::Cpu) {
-            panic!("Network or GPU proving not yet implemented for Nexus. Use CPU resource type.");
-        }
-        Ok(Self { program })
+        Self::check_prover_resource(&resource)?;
+        Ok(Self { program, resource })
     }
 }
 
 impl zkVM for EreNexus {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let elf = ElfFile::from_bytes(self.program.elf()).map_err(Error::ParseElf)?;
 
         // Nexus sdk does not provide a trace, so we need to use core `nvm`
@@ -67,32 +72,60 @@ impl zkVM for EreNexus {
             .public_output()
             .map_err(|err| CommonError::deserialize("public_values", "postcard", err))?;
 
+        // The guest output is recorded as debug logs in the view.
+        let stdout = view
+            .logs()
+            .map_err(|err| Error::ReadLogs(err.to_string()))?
+            .join("");
+        let guest_output = GuestOutput {
+            stdout,
+            stderr: String::new(),
+        };
+
+        let total_num_cycles = trace.get_num_steps() as u64;
+        // The SDK can't stop the execution at a limit, so the limit is only
+        // checked after execution, and a guest that never halts isn't stopped.
+        options.check_cycles(total_num_cycles)?;
+
         Ok((
             public_values,
             ProgramExecutionReport {
-                total_num_cycles: trace.get_num_steps() as u64,
+                total_num_cycles,
+                region_cycles: region_cycles(&guest_output.stdout),
                 execution_duration,
-                ..Default::default()
+                guest_output: Some(guest_output),
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
+        }
+
+        // Execute first to check the cycle limit, since the prover doesn't
+        // support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
         }
 
         let elf = ElfFile::from_bytes(self.program.elf()).map_err(Error::ParseElf)?;
 
+        options.report_progress(ProvePhase::Setup);
+        reset_peak_memory();
+        let start = Instant::now();
         let prover = Stwo::new(&elf).map_err(Error::Prove)?;
+        let setup_time = start.elapsed();
 
+        options.report_progress(ProvePhase::Prove);
         let start = Instant::now();
         let (view, proof) = prover.prove_with_input(&input, &()).map_err(Error::Prove)?;
         let proving_time = start.elapsed();
@@ -109,26 +142,32 @@ impl zkVM for EreNexus {
         let proof_bytes = bincode::serde::encode_to_vec(&proof_bundle, bincode::config::legacy())
             .map_err(|err| CommonError::serialize("proof", "bincode", err))?;
 
-        Ok((
-            proof_bundle.public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(proving_time),
-        ))
+        let proof = Proof::Compressed(proof_bytes);
+        let report = ProgramProvingReport::new(proving_time)
+            .with_setup_time(setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
+
+        Ok((proof_bundle.public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
+
         let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
+            return Err(
+                CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+            );
         };
 
         info!("Verifying proof...");
 
         let (proof_bundle, _): (NexusProofBundle, _) =
-            bincode::serde::decode_from_slice(proof, bincode::config::legacy())
-                .map_err(|err| CommonError::deserialize("proof", "bincode", err))?;
+            bincode::serde::decode_from_slice(proof, bincode::config::legacy()).map_err(|err| {
+                zkVMError::invalid_proof(CommonError::deserialize("proof", "bincode", err))
+            })?;
 
         proof_bundle
             .proof
@@ -155,11 +194,30 @@ impl zkVM for EreNexus {
     }
 }
 
+impl zkVMCapabilities for EreNexus {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
+impl zkVMProgramDigest for EreNexus {
+    /// SHA-256 digest of the ELF, which the proof is verified against.
+    type ProgramDigest = [u8; 32];
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(Sha256::digest(self.program.elf()).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{compiler::RustRv32i, program::NexusProgram, zkvm::EreNexus};
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
@@ -198,6 +256,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreNexus::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -207,6 +274,16 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreNexus::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreNexus::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
@@ -1,4 +1,4 @@
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use nexus_sdk::stwo::seq::Error as StwoError;
 use nexus_vm::error::VMError;
 use thiserror::Error;
@@ -15,6 +15,9 @@ pub enum Error {
     #[error("Nexus execution failed: {0}")]
     Execute(#[source] VMError),
 
+    #[error("Read guest logs failed: {0}")]
+    ReadLogs(String),
+
     // Prove
     #[error("Nexus proving failed: {0}")]
     Prove(#[source] StwoError),
@@ -23,3 +26,14 @@ pub enum Error {
     #[error("Nexus verification failed: {0}")]
     Verify(#[source] StwoError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::Verify(_) => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use crate::program::OpenVMProgram;
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, KeyCache, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    mock_prove, mock_verify, peak_memory, reset_peak_memory, zkVM, zkVMCapabilities, zkVMError,
+    zkVMProgramDigest,
 };
 use openvm_circuit::arch::instructions::exe::VmExe;
-use openvm_continuations::verifier::internal::types::VmStarkProof;
 use openvm_sdk::{
-    CpuSdk, F, SC, StdIn,
-    codec::{Decode, Encode},
+    CpuSdk, F, StdIn,
+    codec::Encode,
     commit::AppExecutionCommit,
     config::{AppConfig, SdkVmConfig},
     fs::read_object_from_file,
@@ -16,11 +16,18 @@ use openvm_sdk::{
 };
 use openvm_stark_sdk::openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::{elf::Elf, openvm_platform::memory::MEM_SIZE};
-use std::{env, path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    env,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 mod error;
+mod verifier;
 
 pub use error::Error;
+pub use verifier::EreOpenVMVerifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
@@ -32,22 +39,13 @@ pub struct EreOpenVM {
     agg_vk: AggVerifyingKey,
     app_commit: AppExecutionCommit,
     resource: ProverResourceType,
+    /// Time spent on app key generation and aggregation key loading
+    setup_time: Duration,
 }
 
 impl EreOpenVM {
     pub fn new(program: OpenVMProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        match resource {
-            #[cfg(not(feature = "cuda"))]
-            ProverResourceType::Gpu => {
-                panic!("Feature `cuda` is disabled. Enable `cuda` to use GPU resource type")
-            }
-            ProverResourceType::Network(_) => {
-                panic!(
-                    "Network proving not yet implemented for OpenVM. Use CPU or GPU resource type."
-                );
-            }
-            _ => {}
-        }
+        Self::check_prover_resource(&resource)?;
 
         let sdk = CpuSdk::new(program.app_config().clone()).map_err(Error::SdkInit)?;
 
@@ -55,12 +53,26 @@ impl EreOpenVM {
 
         let app_exe = sdk.convert_to_exe(elf).map_err(Error::Transpile)?;
 
-        let (app_pk, _) = sdk.app_keygen();
+        // App key depends on both the ELF and the app config.
+        let app_config = toml::to_string(program.app_config())
+            .map_err(|err| CommonError::serialize("app_config", "toml", err))?;
+        let program_bytes = [program.elf(), app_config.as_bytes()].concat();
+
+        let start = Instant::now();
+        let app_pk = KeyCache::from_env().get_or_insert_with(
+            NAME,
+            SDK_VERSION,
+            &program_bytes,
+            "app_pk",
+            || Ok::<_, Error>(sdk.app_keygen().0),
+        )?;
 
         let agg_pk = read_object_from_file::<AggProvingKey, _>(agg_pk_path())
             .map_err(Error::ReadAggKeyFailed)?;
         let agg_vk = agg_pk.get_agg_vk();
+        let setup_time = start.elapsed();
 
+        let _ = sdk.set_app_pk(app_pk.clone());
         let _ = sdk.set_agg_pk(agg_pk.clone());
 
         let app_commit = sdk
@@ -76,6 +88,7 @@ impl EreOpenVM {
             agg_vk,
             app_commit,
             resource,
+            setup_time,
         })
     }
 
@@ -98,63 +111,89 @@ impl EreOpenVM {
 }
 
 impl zkVM for EreOpenVM {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let mut stdin = StdIn::default();
         stdin.write_bytes(input);
 
         let start = Instant::now();
-        let public_values = self
+        let (public_values, (_, total_num_cycles)) = self
             .cpu_sdk()?
-            .execute(self.app_exe.clone(), stdin)
+            .execute_metered_cost(self.app_exe.clone(), stdin)
             .map_err(Error::Execute)?;
+        let execution_duration = start.elapsed();
+
+        // The executor doesn't support a limit on instructions, so the limit
+        // is checked after execution.
+        options.check_cycles(total_num_cycles)?;
 
         Ok((
             public_values,
             ProgramExecutionReport {
-                execution_duration: start.elapsed(),
+                total_num_cycles,
+                execution_duration,
                 ..Default::default()
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
+        }
+
+        // Execute first to check the cycle limit, since the prover doesn't
+        // support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
         }
 
         let mut stdin = StdIn::default();
         stdin.write_bytes(input);
 
-        let now = std::time::Instant::now();
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
+        let now = Instant::now();
         let (proof, app_commit) = match self.resource {
             ProverResourceType::Cpu => self.cpu_sdk()?.prove(self.app_exe.clone(), stdin),
             #[cfg(feature = "cuda")]
             ProverResourceType::Gpu => self.gpu_sdk()?.prove(self.app_exe.clone(), stdin),
             #[cfg(not(feature = "cuda"))]
             ProverResourceType::Gpu => {
-                panic!("Feature `cuda` is disabled. Enable `cuda` to use GPU resource type")
+                return Err(CommonError::unsupported_prover_resource_kind(
+                    ProverResourceKind::Gpu,
+                    Self::SUPPORTED_PROVER_RESOURCE_KINDS.iter().copied(),
+                )
+                .into());
             }
-            ProverResourceType::Network(_) => {
-                panic!(
-                    "Network proving not yet implemented for OpenVM. Use CPU or GPU resource type."
-                );
+            ProverResourceType::Network(_) | ProverResourceType::Mock => {
+                return Err(CommonError::unsupported_prover_resource_kind(
+                    self.resource.kind(),
+                    Self::SUPPORTED_PROVER_RESOURCE_KINDS.iter().copied(),
+                )
+                .into());
             }
         }
         .map_err(Error::Prove)?;
         let elapsed = now.elapsed();
 
         if app_commit != self.app_commit {
-            bail!(Error::UnexpectedAppCommit {
+            return Err(Error::UnexpectedAppCommit {
                 preprocessed: self.app_commit.into(),
                 proved: app_commit.into(),
-            });
+            }
+            .into());
         }
 
         let public_values = extract_public_values(&proof.user_public_values)?;
@@ -162,29 +201,21 @@ impl zkVM for EreOpenVM {
             .encode_to_vec()
             .map_err(|err| CommonError::serialize("proof", "openvm_sdk", err))?;
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(elapsed),
-        ))
-    }
-
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
-        };
+        let proof = Proof::Compressed(proof_bytes);
+        let report = ProgramProvingReport::new(elapsed)
+            .with_setup_time(self.setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
 
-        let proof = VmStarkProof::<SC>::decode(&mut proof.as_slice())
-            .map_err(|err| CommonError::deserialize("proof", "openvm_sdk", err))?;
-
-        CpuSdk::verify_proof(&self.agg_vk, self.app_commit, &proof).map_err(Error::Verify)?;
+        Ok((public_values, proof, report))
+    }
 
-        let public_values = extract_public_values(&proof.user_public_values)?;
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
 
-        Ok(public_values)
+        verifier::verify(&self.agg_vk, self.app_commit, proof)
     }
 
     fn name(&self) -> &'static str {
@@ -196,10 +227,25 @@ impl zkVM for EreOpenVM {
     }
 }
 
+impl zkVMCapabilities for EreOpenVM {
+    /// GPU is only supported when feature `cuda` is enabled.
+    #[cfg(feature = "cuda")]
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] = &[
+        ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu,
+        ProverResourceKind::Mock,
+    ];
+    #[cfg(not(feature = "cuda"))]
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
 impl zkVMProgramDigest for EreOpenVM {
     type ProgramDigest = AppExecutionCommit;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.app_commit)
     }
 }
@@ -223,14 +269,21 @@ fn agg_pk_path() -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use crate::{compiler::RustRv32imaCustomized, program::OpenVMProgram, zkvm::EreOpenVM};
+    use crate::{
+        compiler::RustRv32imaCustomized,
+        program::OpenVMProgram,
+        zkvm::{EreOpenVM, EreOpenVMVerifier},
+    };
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_with_verifier, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{ProofKind, ProverResourceType, zkVM, zkVMProgramDigest},
     };
     use std::sync::OnceLock;
 
@@ -264,6 +317,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreOpenVM::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -282,4 +344,15 @@ mod tests {
             zkvm.prove(&input, ProofKind::default()).unwrap_err();
         }
     }
+
+    #[test]
+    fn test_prove_with_verifier() {
+        let program = basic_program();
+        let zkvm = EreOpenVM::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier =
+            EreOpenVMVerifier::with_default_agg_vk(zkvm.program_digest().unwrap()).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_verifier(&zkvm, &verifier, &test_case);
+    }
 }
@@ -1,4 +1,4 @@
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use openvm_sdk::{SdkError, commit::AppExecutionCommit};
 use thiserror::Error;
 
@@ -44,3 +44,16 @@ pub enum Error {
     #[error("OpenVM verification failed: {0}")]
     Verify(#[source] SdkError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::InvalidPublicValue | Error::UnexpectedAppCommit { .. } | Error::Verify(_) => {
+                zkVMError::invalid_proof(err)
+            }
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::zkvm::{Error, NAME, SDK_VERSION, agg_pk_path, extract_public_values};
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+use openvm_continuations::verifier::internal::types::VmStarkProof;
+use openvm_sdk::{
+    CpuSdk, SC,
+    codec::Decode,
+    commit::AppExecutionCommit,
+    fs::read_object_from_file,
+    keygen::{AggProvingKey, AggVerifyingKey},
+};
+use std::path::PathBuf;
+
+/// Verifier of OpenVM proofs created from [`AppExecutionCommit`] and
+/// [`AggVerifyingKey`] only.
+pub struct EreOpenVMVerifier {
+    app_commit: AppExecutionCommit,
+    agg_vk: AggVerifyingKey,
+}
+
+impl EreOpenVMVerifier {
+    pub fn new(app_commit: AppExecutionCommit, agg_vk: AggVerifyingKey) -> Self {
+        Self { app_commit, agg_vk }
+    }
+
+    /// Creates verifier with the [`AggVerifyingKey`] generated by
+    /// `cargo openvm setup`.
+    ///
+    /// It reads `~/.openvm/agg_stark.vk` if exists, otherwise it falls back to
+    /// extract it from `~/.openvm/agg_stark.pk`.
+    pub fn with_default_agg_vk(app_commit: AppExecutionCommit) -> Result<Self, Error> {
+        let agg_vk = if agg_vk_path().exists() {
+            read_object_from_file::<AggVerifyingKey, _>(agg_vk_path())
+                .map_err(Error::ReadAggKeyFailed)?
+        } else {
+            read_object_from_file::<AggProvingKey, _>(agg_pk_path())
+                .map_err(Error::ReadAggKeyFailed)?
+                .get_agg_vk()
+        };
+        Ok(Self::new(app_commit, agg_vk))
+    }
+}
+
+impl zkVMVerifier for EreOpenVMVerifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(&self.agg_vk, self.app_commit, proof)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreOpenVMVerifier {
+    type ProgramDigest = AppExecutionCommit;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.app_commit)
+    }
+}
+
+pub fn verify(
+    agg_vk: &AggVerifyingKey,
+    app_commit: AppExecutionCommit,
+    proof: &Proof,
+) -> Result<PublicValues, zkVMError> {
+    let Proof::Compressed(proof) = proof else {
+        return Err(
+            CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+        );
+    };
+
+    let proof = VmStarkProof::<SC>::decode(&mut proof.as_slice()).map_err(|err| {
+        zkVMError::invalid_proof(CommonError::deserialize("proof", "openvm_sdk", err))
+    })?;
+
+    CpuSdk::verify_proof(agg_vk, app_commit, &proof).map_err(Error::Verify)?;
+
+    let public_values = extract_public_values(&proof.user_public_values)?;
+
+    Ok(public_values)
+}
+
+fn agg_vk_path() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").expect("env `$HOME` should be set"))
+        .join(".openvm/agg_stark.vk")
+}
@@ -2,10 +2,11 @@ use crate::{
     program::PicoProgram,
     zkvm::sdk::{BaseVerifyingKey, MetaProof, ProverClient},
 };
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
+    ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues, mock_prove,
+    mock_verify, peak_memory, reset_peak_memory, zkVM, zkVMCapabilities, zkVMError,
+    zkVMProgramDigest,
 };
 use pico_p3_field::PrimeField32;
 use pico_vm::emulator::stdin::EmulatorStdinBuilder;
@@ -28,14 +29,13 @@ pub struct PicoProofWithPublicValues {
 
 pub struct ErePico {
     program: PicoProgram,
+    resource: ProverResourceType,
 }
 
 impl ErePico {
     pub fn new(program: PicoProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if !matches!(resource, ProverResourceType::Cpu) {
-            panic!("Network or GPU proving not yet implemented for Pico. Use CPU resource type.");
-        }
-        Ok(ErePico { program })
+        Self::check_prover_resource(&resource)?;
+        Ok(ErePico { program, resource })
     }
 
     pub fn client(&self) -> ProverClient {
@@ -44,7 +44,11 @@ impl ErePico {
 }
 
 impl zkVM for ErePico {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let mut stdin = EmulatorStdinBuilder::default();
         stdin.write_slice(input);
 
@@ -56,8 +60,14 @@ impl zkVM for ErePico {
         })
         .map_err(|err| Error::ExecutePanic(panic_msg(err)))?;
 
+        // The SDK can't stop the execution at a limit, so the limit is only
+        // checked after execution, and a guest that never halts isn't stopped.
+        options.check_cycles(total_num_cycles)?;
+
         Ok((
             public_values,
+            // Region cycles are not reported, the executor prints the guest
+            // output to the process stdout instead of returning it.
             ProgramExecutionReport {
                 total_num_cycles,
                 execution_duration,
@@ -66,26 +76,37 @@ impl zkVM for ErePico {
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.resource.is_mock() {
+            return mock_prove(self, input, proof_kind, options);
+        }
+
+        // Execute first to check the cycle limit, since the prover doesn't
+        // support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
         }
 
         let mut stdin = EmulatorStdinBuilder::default();
         stdin.write_slice(input);
 
-        let ((public_values, proof), proving_time) = panic::catch_unwind(|| {
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
+        let ((public_values, proof), setup_time, proving_time) = panic::catch_unwind(|| {
+            let start = Instant::now();
             let client = self.client();
+            let setup_time = start.elapsed();
             let start = Instant::now();
             let result = client.prove(stdin)?;
-            Ok((result, start.elapsed()))
+            Ok((result, setup_time, start.elapsed()))
         })
         .map_err(|err| Error::ProvePanic(panic_msg(err)))?
         .map_err(Error::Prove)?;
@@ -99,33 +120,39 @@ impl zkVM for ErePico {
         )
         .map_err(|err| CommonError::serialize("proof", "bincode", err))?;
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof_bytes),
-            ProgramProvingReport::new(proving_time),
-        ))
+        let proof = Proof::Compressed(proof_bytes);
+        let report = ProgramProvingReport::new(proving_time)
+            .with_setup_time(setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
+
+        Ok((public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.resource.is_mock() {
+            return mock_verify(proof);
+        }
+
         let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
+            return Err(
+                CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+            );
         };
 
         let client = self.client();
 
         let (proof, _): (PicoProofWithPublicValues, _) =
-            bincode::serde::decode_from_slice(proof, bincode::config::legacy())
-                .map_err(|err| CommonError::deserialize("proof", "bincode", err))?;
+            bincode::serde::decode_from_slice(proof, bincode::config::legacy()).map_err(|err| {
+                zkVMError::invalid_proof(CommonError::deserialize("proof", "bincode", err))
+            })?;
 
         client.verify(&proof.proof).map_err(Error::Verify)?;
 
         let claimed = <[u8; 32]>::from(Sha256::digest(&proof.public_values));
         let proved = extract_public_values_sha256_digest(&proof.proof)?;
         if claimed != proved {
-            bail!(Error::UnexpectedPublicValuesDigest { claimed, proved });
+            return Err(Error::UnexpectedPublicValuesDigest { claimed, proved }.into());
         }
 
         Ok(proof.public_values)
@@ -140,10 +167,17 @@ impl zkVM for ErePico {
     }
 }
 
+impl zkVMCapabilities for ErePico {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
 impl zkVMProgramDigest for ErePico {
     type ProgramDigest = BaseVerifyingKey;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.client().vk().clone())
     }
 }
@@ -181,7 +215,10 @@ fn panic_msg(err: Box<dyn std::any::Any + Send + 'static>) -> String {
 mod tests {
     use crate::{compiler::RustRv32imaCustomized, program::PicoProgram, zkvm::ErePico};
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
@@ -220,6 +257,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = ErePico::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -229,6 +275,16 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = ErePico::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = ErePico::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
@@ -1,4 +1,4 @@
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -33,3 +33,18 @@ pub enum Error {
     #[error("Unexpected public value digest - claimed: {claimed:?}, proved: {proved:?}")]
     UnexpectedPublicValuesDigest { claimed: [u8; 32], proved: [u8; 32] },
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::ExecutePanic(_) => zkVMError::guest_panic(err, None),
+            Error::Verify(_)
+            | Error::InvalidBaseProofLength(_)
+            | Error::InvalidPublicValuesLength(_)
+            | Error::InvalidPublicValues
+            | Error::UnexpectedPublicValuesDigest { .. } => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
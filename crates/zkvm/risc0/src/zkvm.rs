@@ -1,18 +1,27 @@
 use crate::program::Risc0Program;
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, GuestOutput, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    region_cycles, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
 };
 use risc0_zkvm::{
-    DEFAULT_MAX_PO2, DefaultProver, Digest, ExecutorEnv, ExternalProver, InnerReceipt, ProverOpts,
-    Receipt, default_executor, default_prover,
+    DEFAULT_MAX_PO2, DefaultProver, Digest, ExecutorEnv, ExitCode, ExternalProver, FakeReceipt,
+    InnerReceipt, ProverOpts, Receipt, SessionInfo, default_executor, default_prover,
 };
-use std::{env, ops::RangeInclusive, rc::Rc, time::Instant};
+use std::{
+    env, fs, io,
+    ops::RangeInclusive,
+    rc::Rc,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+use tracing::warn;
 
 mod error;
+mod verifier;
 
 pub use error::Error;
+pub use verifier::EreRisc0Verifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
@@ -51,11 +60,7 @@ pub struct EreRisc0 {
 
 impl EreRisc0 {
     pub fn new(program: Risc0Program, resource: ProverResourceType) -> Result<Self, Error> {
-        if matches!(resource, ProverResourceType::Network(_)) {
-            panic!(
-                "Network proving not yet implemented for RISC Zero. Use CPU or GPU resource type."
-            );
-        }
+        Self::check_prover_resource(&resource)?;
 
         let [segment_po2, keccak_po2] = [
             ("RISC0_SEGMENT_PO2", DEFAULT_SEGMENT_PO2, SEGMENT_PO2_RANGE),
@@ -67,10 +72,11 @@ impl EreRisc0 {
                 .and_then(|po2| po2.parse::<usize>().ok())
                 .unwrap_or(default);
             if !range.contains(&val) {
-                panic!("Unsupported po2 value {val} of {key}, expected in range {range:?}")
+                return Err(Error::UnsupportedPo2 { key, val, range });
             }
-            val
+            Ok(val)
         });
+        let [segment_po2, keccak_po2] = [segment_po2?, keccak_po2?];
 
         Ok(Self {
             program,
@@ -79,39 +85,99 @@ impl EreRisc0 {
             keccak_po2,
         })
     }
-}
 
-impl zkVM for EreRisc0 {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    /// Executes the program, returns the [`SessionInfo`] and the guest output
+    /// captured along with the execution duration.
+    fn execute_session(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(SessionInfo, GuestOutput, Duration), zkVMError> {
         let executor = default_executor();
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
         let env = ExecutorEnv::builder()
             .write_slice(input)
+            .session_limit(options.cycle_limit)
+            .stdout(&mut stdout)
+            .stderr(&mut stderr)
             .build()
             .map_err(Error::BuildExecutorEnv)?;
 
         let start = Instant::now();
         let session_info = executor
             .execute(env, &self.program.elf)
-            .map_err(Error::Execute)?;
-
-        let public_values = session_info.journal.bytes.clone();
+            .map_err(|err| match options.cycle_limit {
+                Some(cycle_limit) if is_session_limit_exceeded(&err) => {
+                    CommonError::CycleLimitExceeded { cycle_limit }.into()
+                }
+                _ => Error::Execute(err),
+            })?;
+        let execution_duration = start.elapsed();
+
+        let guest_output = GuestOutput::new(&stdout, &stderr);
+
+        // The guest could exit with non-zero code by `env::exit` without
+        // panicking, which is still a failed execution.
+        if let ExitCode::Halted(exit_code @ 1..) = session_info.exit_code {
+            return Err(Error::GuestPanic {
+                message: guest_output.stderr,
+                exit_code,
+            }
+            .into());
+        }
 
-        Ok((
-            public_values,
-            ProgramExecutionReport {
-                total_num_cycles: session_info.cycles() as u64,
-                execution_duration: start.elapsed(),
-                ..Default::default()
-            },
-        ))
+        Ok((session_info, guest_output, execution_duration))
     }
 
-    fn prove(
+    /// Executes the program and creates a [`FakeReceipt`] of the session,
+    /// which is only accepted by verifier in dev mode.
+    fn mock_prove(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        options.report_progress(ProvePhase::Execute);
+        let (session_info, _, proving_time) =
+            self.execute_session(input, &options.execute_options())?;
+
+        let claim = session_info
+            .receipt_claim
+            .ok_or(Error::MissingReceiptClaim)?;
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(claim)),
+            session_info.journal.bytes,
+        );
+
+        let public_values = receipt.journal.bytes.clone();
+        let proof = Proof::new(
+            proof_kind,
+            borsh::to_vec(&receipt).map_err(|err| CommonError::serialize("proof", "borsh", err))?,
+        );
+
+        let report = ProgramProvingReport::new(proving_time)
+            .with_proof_size(&proof)
+            .with_num_segments(session_info.segments.len() as u64);
+
+        Ok((public_values, proof, report))
+    }
+
+    /// Proves with the prover of the resource, returns the receipt and the
+    /// number of segments.
+    ///
+    /// The prover and `ExecutorEnv` are created here since they are not
+    /// `Send`, and the `r0vm` spawned should be a child of the calling thread.
+    fn prove_receipt(&self, input: &[u8], opts: &ProverOpts) -> Result<(Receipt, u64), zkVMError> {
+        let env = ExecutorEnv::builder()
+            .write_slice(input)
+            .segment_limit_po2(self.segment_po2 as _)
+            .keccak_max_po2(self.keccak_po2 as _)
+            .and_then(|builder| builder.build())
+            .map_err(Error::BuildExecutorEnv)?;
+
         let prover = match self.resource {
+            // `r0vm` proves the whole session in a single request, so the
+            // segments are not reported.
             ProverResourceType::Cpu => Rc::new(ExternalProver::new("ipc", "r0vm")),
             ProverResourceType::Gpu => {
                 if cfg!(feature = "metal") {
@@ -127,73 +193,115 @@ impl zkVM for EreRisc0 {
                     Rc::new(DefaultProver::new("r0vm-cuda").map_err(Error::InitializeCudaProver)?)
                 }
             }
-            ProverResourceType::Network(_) => {
-                panic!(
-                    "Network proving not yet implemented for RISC Zero. Use CPU or GPU resource type."
-                );
+            ProverResourceType::Network(_) | ProverResourceType::Mock => {
+                return Err(CommonError::unsupported_prover_resource_kind(
+                    self.resource.kind(),
+                    Self::SUPPORTED_PROVER_RESOURCE_KINDS.iter().copied(),
+                )
+                .into());
             }
         };
 
-        let env = ExecutorEnv::builder()
-            .write_slice(input)
-            .segment_limit_po2(self.segment_po2 as _)
-            .keccak_max_po2(self.keccak_po2 as _)
-            .and_then(|builder| builder.build())
-            .map_err(Error::BuildExecutorEnv)?;
+        let prove_info = prover
+            .prove_with_opts(env, &self.program.elf, opts)
+            .map_err(Error::Prove)?;
+
+        Ok((prove_info.receipt, prove_info.stats.segments as u64))
+    }
+}
+
+impl zkVM for EreRisc0 {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
+        let (session_info, guest_output, execution_duration) =
+            self.execute_session(input, options)?;
+
+        let public_values = session_info.journal.bytes.clone();
+
+        Ok((
+            public_values,
+            ProgramExecutionReport {
+                total_num_cycles: session_info.cycles() as u64,
+                region_cycles: region_cycles(&guest_output.stdout),
+                execution_duration,
+                guest_output: Some(guest_output),
+            },
+        ))
+    }
+
+    fn prove_with_options(
+        &self,
+        input: &[u8],
+        proof_kind: ProofKind,
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        if self.resource.is_mock() {
+            return self.mock_prove(input, proof_kind, options);
+        }
+
+        // Execute first to enforce the cycle limit, since the session limit
+        // error can't be told apart from others when proving by `r0vm`.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
+        }
+
+        let cancellation = options.cancellation();
 
         let opts = match proof_kind {
             ProofKind::Compressed => ProverOpts::succinct(),
             ProofKind::Groth16 => ProverOpts::groth16(),
+            ProofKind::Core => ProverOpts::composite(),
+            ProofKind::Plonk => {
+                return Err(CommonError::unsupported_proof_kind(
+                    proof_kind,
+                    Self::SUPPORTED_PROOF_KINDS.iter().copied(),
+                )
+                .into());
+            }
         };
 
+        // Thread id of the proving thread, to find the `r0vm` it spawns when
+        // the proving is cancelled.
+        // Only `Cpu` and `Gpu` with CUDA prove by `r0vm`, `Gpu` with `metal`
+        // proves in this process, so the cancellation is not honoured.
+        let proving_thread = OnceLock::new();
+
+        options.report_progress(ProvePhase::Prove);
         let now = Instant::now();
-        let prove_info = prover
-            .prove_with_opts(env, &self.program.elf, &opts)
-            .map_err(Error::Prove)?;
+        let (receipt, num_segments) = cancellation.run(
+            || {
+                let _ = proving_thread.set(thread_self());
+                self.prove_receipt(input, &opts)
+            },
+            || {
+                if let Some(Some(tid)) = proving_thread.get() {
+                    kill_child_processes(tid);
+                }
+            },
+        )??;
         let proving_time = now.elapsed();
 
-        let public_values = prove_info.receipt.journal.bytes.clone();
+        let public_values = receipt.journal.bytes.clone();
         let proof = Proof::new(
             proof_kind,
-            borsh::to_vec(&prove_info.receipt)
-                .map_err(|err| CommonError::serialize("proof", "borsh", err))?,
+            borsh::to_vec(&receipt).map_err(|err| CommonError::serialize("proof", "borsh", err))?,
         );
 
-        Ok((
-            public_values,
-            proof,
-            ProgramProvingReport::new(proving_time),
-        ))
-    }
-
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let proof_kind = proof.kind();
-
-        let receipt: Receipt = borsh::from_slice(proof.as_bytes())
-            .map_err(|err| CommonError::deserialize("proof", "borsh", err))?;
-
-        if !matches!(
-            (proof_kind, &receipt.inner),
-            (ProofKind::Compressed, InnerReceipt::Succinct(_))
-                | (ProofKind::Groth16, InnerReceipt::Groth16(_))
-        ) {
-            let got = match &receipt.inner {
-                InnerReceipt::Composite(_) => "Composite",
-                InnerReceipt::Succinct(_) => "Succinct",
-                InnerReceipt::Groth16(_) => "Groth16",
-                InnerReceipt::Fake(_) => "Fake",
-                _ => "Unknown",
-            };
-            bail!(Error::InvalidProofKind(proof_kind, got.to_string()));
-        }
-
-        receipt
-            .verify(self.program.image_id)
-            .map_err(Error::Verify)?;
+        // Peak memory is not reported since the proving is mostly done by
+        // `r0vm` in another process.
+        let report = ProgramProvingReport::new(proving_time)
+            .with_proof_size(&proof)
+            .with_num_segments(num_segments);
 
-        let public_values = receipt.journal.bytes.clone();
+        Ok((public_values, proof, report))
+    }
 
-        Ok(public_values)
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verifier::verify(self.program.image_id, proof, self.resource.is_mock())
     }
 
     fn name(&self) -> &'static str {
@@ -205,24 +313,86 @@ impl zkVM for EreRisc0 {
     }
 }
 
+/// Returns id of the current thread, `None` if it's not available (only on
+/// Linux).
+fn thread_self() -> Option<String> {
+    let path = fs::read_link("/proc/thread-self").ok()?;
+    Some(path.file_name()?.to_str()?.to_string())
+}
+
+/// Kills the child processes spawned by thread `tid` of the current process.
+///
+/// `ExternalProver` and `DefaultProver` spawn `r0vm` (or `r0vm-cuda`) for each
+/// request without exposing the child process, so we can't hold its handle
+/// and find it by `/proc/self/task/{tid}/children` instead. Once `r0vm` is
+/// killed, the prover returns early with an error.
+fn kill_child_processes(tid: &str) {
+    let Ok(children) = fs::read_to_string(format!("/proc/self/task/{tid}/children")) else {
+        return;
+    };
+    for pid in children.split_whitespace() {
+        let Ok(pid) = pid.parse::<libc::pid_t>() else {
+            continue;
+        };
+        // SAFETY: `kill` only sends a signal, the pid is a child of ours.
+        if unsafe { libc::kill(pid, libc::SIGKILL) } != 0 {
+            warn!(
+                "Failed to kill child process {pid}: {}",
+                io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+/// Returns whether the execution error is caused by exceeding the session
+/// limit set by [`ExecutorEnvBuilder::session_limit`].
+///
+/// The executor reports it as an untyped `anyhow` error, so we can only tell
+/// it by the message.
+///
+/// [`ExecutorEnvBuilder::session_limit`]: risc0_zkvm::ExecutorEnvBuilder::session_limit
+fn is_session_limit_exceeded(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|err| err.to_string().contains("Session limit exceeded"))
+}
+
+impl zkVMCapabilities for EreRisc0 {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] = &[
+        ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu,
+        ProverResourceKind::Mock,
+    ];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] =
+        &[ProofKind::Compressed, ProofKind::Groth16, ProofKind::Core];
+}
+
 impl zkVMProgramDigest for EreRisc0 {
     type ProgramDigest = Digest;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.program.image_id)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{compiler::RustRv32imaCustomized, program::Risc0Program, zkvm::EreRisc0};
+    use crate::{
+        compiler::RustRv32imaCustomized,
+        program::Risc0Program,
+        zkvm::{EreRisc0, EreRisc0Verifier},
+    };
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, run_zkvm_prove_with_proof_kind, run_zkvm_prove_with_verifier,
+            run_zkvm_region_cycles, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{ProofKind, ProverResourceType, zkVM, zkVMProgramDigest},
     };
     use std::sync::OnceLock;
 
@@ -246,6 +416,15 @@ mod tests {
         run_zkvm_execute(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_region_cycles() {
+        let program = basic_program();
+        let zkvm = EreRisc0::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_region_cycles(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_execute_invalid_input() {
         let program = basic_program();
@@ -256,6 +435,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreRisc0::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -265,6 +453,25 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_core() {
+        let program = basic_program();
+        let zkvm = EreRisc0::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_proof_kind(&zkvm, &test_case, ProofKind::Core);
+    }
+
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreRisc0::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreRisc0::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
@@ -275,6 +482,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prove_with_verifier() {
+        let program = basic_program();
+        let zkvm = EreRisc0::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier = EreRisc0Verifier::new(zkvm.program_digest().unwrap());
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_verifier(&zkvm, &verifier, &test_case);
+    }
+
     #[test]
     fn test_aligned_allocs() {
         let program = RustRv32imaCustomized
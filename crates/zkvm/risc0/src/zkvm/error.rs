@@ -1,9 +1,20 @@
-use ere_zkvm_interface::zkvm::ProofKind;
+use ere_zkvm_interface::zkvm::{CommonError, ProofKind, zkVMError};
 use risc0_zkp::verify::VerificationError;
+use std::ops::RangeInclusive;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error(transparent)]
+    CommonError(#[from] CommonError),
+
+    #[error("Unsupported po2 value {val} of {key}, expected in range {range:?}")]
+    UnsupportedPo2 {
+        key: &'static str,
+        val: usize,
+        range: RangeInclusive<usize>,
+    },
+
     // Execute
     #[error("Failed to build `ExecutorEnv`: {0}")]
     BuildExecutorEnv(anyhow::Error),
@@ -11,6 +22,9 @@ pub enum Error {
     #[error("Failed to execute: {0}")]
     Execute(anyhow::Error),
 
+    #[error("Guest exited with code {exit_code}: {message}")]
+    GuestPanic { message: String, exit_code: u32 },
+
     // Prove
     #[error("Failed to initialize cuda prover: {0}")]
     InitializeCudaProver(anyhow::Error),
@@ -18,6 +32,9 @@ pub enum Error {
     #[error("Failed to prove: {0}")]
     Prove(anyhow::Error),
 
+    #[error("Receipt claim is missing from the execution session")]
+    MissingReceiptClaim,
+
     // Verify
     #[error("Invalid proof kind, expected: {0:?}, got: {1}")]
     InvalidProofKind(ProofKind, String),
@@ -25,3 +42,18 @@ pub enum Error {
     #[error("Failed to verify: {0}")]
     Verify(VerificationError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::GuestPanic { message, exit_code } => {
+                zkVMError::guest_panic(message, Some(exit_code as i32))
+            }
+            Error::InvalidProofKind(..) | Error::Verify(_) => zkVMError::invalid_proof(err),
+            Error::UnsupportedPo2 { .. } => zkVMError::unsupported(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::zkvm::{Error, NAME, SDK_VERSION};
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+use risc0_zkvm::{Digest, InnerReceipt, Receipt, VerifierContext};
+
+/// Verifier of Risc0 proofs created from image id only.
+pub struct EreRisc0Verifier {
+    image_id: Digest,
+}
+
+impl EreRisc0Verifier {
+    pub fn new(image_id: Digest) -> Self {
+        Self { image_id }
+    }
+}
+
+impl zkVMVerifier for EreRisc0Verifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(self.image_id, proof, false)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreRisc0Verifier {
+    type ProgramDigest = Digest;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.image_id)
+    }
+}
+
+/// Verifies the proof against `image_id`.
+///
+/// The [`InnerReceipt::Fake`] created in [`ProverResourceType::Mock`] mode is
+/// only accepted when `mock` is `true`.
+///
+/// [`ProverResourceType::Mock`]: ere_zkvm_interface::zkvm::ProverResourceType::Mock
+pub fn verify(image_id: Digest, proof: &Proof, mock: bool) -> Result<PublicValues, zkVMError> {
+    let proof_kind = proof.kind();
+
+    let receipt: Receipt = borsh::from_slice(proof.as_bytes())
+        .map_err(|err| zkVMError::invalid_proof(CommonError::deserialize("proof", "borsh", err)))?;
+
+    if mock {
+        if !matches!(receipt.inner, InnerReceipt::Fake(_)) {
+            return Err(CommonError::InvalidMockProof.into());
+        }
+    } else if !matches!(
+        (proof_kind, &receipt.inner),
+        (ProofKind::Compressed, InnerReceipt::Succinct(_))
+            | (ProofKind::Groth16, InnerReceipt::Groth16(_))
+            | (ProofKind::Core, InnerReceipt::Composite(_))
+    ) {
+        let got = match &receipt.inner {
+            InnerReceipt::Composite(_) => "Composite",
+            InnerReceipt::Succinct(_) => "Succinct",
+            InnerReceipt::Groth16(_) => "Groth16",
+            InnerReceipt::Fake(_) => "Fake",
+            _ => "Unknown",
+        };
+        return Err(Error::InvalidProofKind(proof_kind, got.to_string()).into());
+    }
+
+    // Set dev mode explicitly, otherwise it'd be enabled by env
+    // `RISC0_DEV_MODE` and accept the fake receipt.
+    let ctx = VerifierContext::default().with_dev_mode(mock);
+    receipt
+        .verify_with_context(&ctx, image_id)
+        .map_err(Error::Verify)?;
+
+    let public_values = receipt.journal.bytes.clone();
+
+    Ok(public_values)
+}
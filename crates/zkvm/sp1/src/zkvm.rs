@@ -1,22 +1,24 @@
 use crate::{program::SP1Program, zkvm::sdk::Prover};
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, KeyCache, ProgramExecutionReport, ProgramProvingReport, Proof,
+    ProofKind, ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues,
+    peak_memory, reset_peak_memory, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
 };
-use sp1_sdk::{SP1ProofMode, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin, SP1VerifyingKey};
+use sp1_sdk::{SP1ProofMode, SP1ProvingKey, SP1Stdin, SP1VerifyingKey};
 use std::{
     mem::take,
     panic,
     sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tracing::info;
 
 mod error;
 mod sdk;
+mod verifier;
 
 pub use error::Error;
+pub use verifier::EreSP1Verifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
@@ -28,6 +30,8 @@ pub struct EreSP1 {
     pk: SP1ProvingKey,
     /// Verification key
     vk: SP1VerifyingKey,
+    /// Time spent on setup to generate proving and verification key
+    setup_time: Duration,
     // The current version of SP1 (v5.2.1) has a problem where if GPU proving
     // the program crashes in the Moongate container, it leaves an internal
     // mutex poisoned, which prevents further proving attempts.
@@ -40,13 +44,23 @@ pub struct EreSP1 {
 
 impl EreSP1 {
     pub fn new(program: SP1Program, resource: ProverResourceType) -> Result<Self, Error> {
-        let prover = Prover::new(&resource);
-        let (pk, vk) = prover.setup(&program.elf);
+        Self::check_prover_resource(&resource)?;
+        let prover = Prover::new(&resource)?;
+        let start = Instant::now();
+        let (pk, vk) = KeyCache::from_env().get_or_insert_with(
+            NAME,
+            SDK_VERSION,
+            &program.elf,
+            "keys",
+            || Ok::<_, Error>(prover.setup(&program.elf)),
+        )?;
+        let setup_time = start.elapsed();
         Ok(Self {
             program,
             resource,
             pk,
             vk,
+            setup_time,
             prover: RwLock::new(prover),
         })
     }
@@ -61,14 +75,19 @@ impl EreSP1 {
 }
 
 impl zkVM for EreSP1 {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let mut stdin = SP1Stdin::new();
         stdin.write_slice(input);
 
         let prover = self.prover()?;
 
         let start = Instant::now();
-        let (public_values, exec_report) = prover.execute(self.program.elf(), &stdin)?;
+        let (public_values, exec_report, guest_output) =
+            prover.execute(self.program.elf(), &stdin, options.cycle_limit)?;
         let execution_duration = start.elapsed();
 
         Ok((
@@ -77,15 +96,24 @@ impl zkVM for EreSP1 {
                 total_num_cycles: exec_report.total_instruction_count(),
                 region_cycles: exec_report.cycle_tracker.into_iter().collect(),
                 execution_duration,
+                guest_output: Some(guest_output),
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        // Execute first to enforce the cycle limit, since not all provers
+        // support it when proving.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
+        }
+
         info!("Generating proof…");
 
         let mut stdin = SP1Stdin::new();
@@ -94,23 +122,31 @@ impl zkVM for EreSP1 {
         let mode = match proof_kind {
             ProofKind::Compressed => SP1ProofMode::Compressed,
             ProofKind::Groth16 => SP1ProofMode::Groth16,
+            ProofKind::Core => SP1ProofMode::Core,
+            ProofKind::Plonk => SP1ProofMode::Plonk,
         };
 
         let mut prover = self.prover_mut()?;
 
+        // The SDK proves the shards in a single call without reporting them,
+        // so only the phase is reported.
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
         let start = Instant::now();
-        let proof =
-            panic::catch_unwind(|| prover.prove(&self.pk, &stdin, mode)).map_err(|err| {
+        let proof = match panic::catch_unwind(|| prover.prove(&self.pk, &stdin, mode)) {
+            Ok(proof) => proof?,
+            Err(err) => {
                 if matches!(self.resource, ProverResourceType::Gpu) {
                     // Drop the panicked prover and create a new one.
                     // Note that `take` has to be done explicitly first so the
                     // Moongate container could be removed properly.
                     take(&mut *prover);
-                    *prover = Prover::new(&self.resource);
+                    *prover = Prover::new(&self.resource)?;
                 }
 
-                Error::Panic(panic_msg(err))
-            })??;
+                return Err(Error::Panic(panic_msg(err)).into());
+            }
+        };
         let proving_time = start.elapsed();
 
         let public_values = proof.public_values.to_vec();
@@ -120,36 +156,16 @@ impl zkVM for EreSP1 {
                 .map_err(|err| CommonError::serialize("proof", "bincode", err))?,
         );
 
-        Ok((
-            public_values,
-            proof,
-            ProgramProvingReport::new(proving_time),
-        ))
-    }
-
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        info!("Verifying proof…");
-
-        let proof_kind = proof.kind();
-
-        let (proof, _): (SP1ProofWithPublicValues, _) =
-            bincode::serde::decode_from_slice(proof.as_bytes(), bincode::config::legacy())
-                .map_err(|err| CommonError::deserialize("proof", "bincode", err))?;
-        let inner_proof_kind = SP1ProofMode::from(&proof.proof);
-
-        if !matches!(
-            (proof_kind, inner_proof_kind),
-            (ProofKind::Compressed, SP1ProofMode::Compressed)
-                | (ProofKind::Groth16, SP1ProofMode::Groth16)
-        ) {
-            bail!(Error::InvalidProofKind(proof_kind, inner_proof_kind));
-        }
+        let report = ProgramProvingReport::new(proving_time)
+            .with_setup_time(self.setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
 
-        self.prover()?.verify(&proof, &self.vk)?;
-
-        let public_values_bytes = proof.public_values.as_slice().to_vec();
+        Ok((public_values, proof, report))
+    }
 
-        Ok(public_values_bytes)
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verifier::verify(&*self.prover()?, &self.vk, proof)
     }
 
     fn name(&self) -> &'static str {
@@ -161,10 +177,26 @@ impl zkVM for EreSP1 {
     }
 }
 
+impl zkVMCapabilities for EreSP1 {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] = &[
+        ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu,
+        ProverResourceKind::Network,
+        ProverResourceKind::Mock,
+    ];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[
+        ProofKind::Compressed,
+        ProofKind::Groth16,
+        ProofKind::Core,
+        ProofKind::Plonk,
+    ];
+}
+
 impl zkVMProgramDigest for EreSP1 {
     type ProgramDigest = SP1VerifyingKey;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.vk.clone())
     }
 }
@@ -177,14 +209,22 @@ fn panic_msg(err: Box<dyn std::any::Any + Send + 'static>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::{compiler::RustRv32imaCustomized, program::SP1Program, zkvm::EreSP1};
+    use crate::{
+        compiler::RustRv32imaCustomized,
+        program::SP1Program,
+        zkvm::{EreSP1, EreSP1Verifier},
+    };
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, run_zkvm_prove_with_proof_kind, run_zkvm_prove_with_verifier,
+            run_zkvm_region_cycles, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{NetworkProverConfig, ProofKind, ProverResourceType, zkVM},
+        zkvm::{NetworkProverConfig, ProofKind, ProverResourceType, zkVM, zkVMProgramDigest},
     };
     use std::sync::OnceLock;
 
@@ -208,6 +248,15 @@ mod tests {
         run_zkvm_execute(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_region_cycles() {
+        let program = basic_program();
+        let zkvm = EreSP1::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_region_cycles(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_execute_invalid_input() {
         let program = basic_program();
@@ -218,6 +267,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreSP1::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -227,6 +285,25 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_core() {
+        let program = basic_program();
+        let zkvm = EreSP1::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_proof_kind(&zkvm, &test_case, ProofKind::Core);
+    }
+
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreSP1::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreSP1::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
@@ -237,6 +314,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prove_with_verifier() {
+        let program = basic_program();
+        let zkvm = EreSP1::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier = EreSP1Verifier::new(zkvm.program_digest().unwrap());
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_verifier(&zkvm, &verifier, &test_case);
+    }
+
     #[test]
     #[ignore = "Requires NETWORK_PRIVATE_KEY environment variable to be set"]
     fn test_prove_sp1_network() {
@@ -1,4 +1,4 @@
-use ere_zkvm_interface::zkvm::{CommonError, ProofKind};
+use ere_zkvm_interface::zkvm::{CommonError, ProofKind, zkVMError};
 use sp1_sdk::{SP1ProofMode, SP1VerificationError};
 use thiserror::Error;
 
@@ -10,10 +10,18 @@ pub enum Error {
     #[error("Prover RwLock posioned, panic not catched properly")]
     RwLockPosioned,
 
+    #[error(
+        "Network proving requires a private key, set NETWORK_PRIVATE_KEY environment variable or provide api_key in NetworkProverConfig"
+    )]
+    NetworkPrivateKeyMissing,
+
     // Execute
     #[error("SP1 execution failed: {0}")]
     Execute(#[source] anyhow::Error),
 
+    #[error("SP1 guest panicked with exit code {exit_code}: {message}")]
+    GuestPanic { message: String, exit_code: u32 },
+
     // Prove
     #[error("SP1 SDK proving failed: {0}")]
     Prove(#[source] anyhow::Error),
@@ -28,3 +36,17 @@ pub enum Error {
     #[error("SP1 SDK verification failed: {0}")]
     Verify(#[source] SP1VerificationError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::GuestPanic { message, exit_code } => {
+                zkVMError::guest_panic(message, Some(exit_code as i32))
+            }
+            Error::InvalidProofKind(..) | Error::Verify(_) => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use crate::zkvm::Error;
-use ere_zkvm_interface::zkvm::{NetworkProverConfig, ProverResourceType};
+use ere_zkvm_interface::zkvm::{CommonError, GuestOutput, NetworkProverConfig, ProverResourceType};
+use sp1_core_executor::ExecutionError;
 use sp1_sdk::{
-    CpuProver, CudaProver, NetworkProver, Prover as _, ProverClient, SP1ProofMode,
-    SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin, SP1VerifyingKey,
+    CpuProver, CudaProver, ExecutionReport, NetworkProver, Prover as _, ProverClient, SP1ProofMode,
+    SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues, SP1Stdin, SP1VerifyingKey,
 };
 
 #[allow(clippy::large_enum_variant)]
@@ -14,17 +15,18 @@ pub enum Prover {
 
 impl Default for Prover {
     fn default() -> Self {
-        Self::new(&ProverResourceType::Cpu)
+        Self::Cpu(ProverClient::builder().cpu().build())
     }
 }
 
 impl Prover {
-    pub fn new(resource: &ProverResourceType) -> Self {
-        match resource {
+    pub fn new(resource: &ProverResourceType) -> Result<Self, Error> {
+        Ok(match resource {
             ProverResourceType::Cpu => Self::Cpu(ProverClient::builder().cpu().build()),
+            ProverResourceType::Mock => Self::Cpu(ProverClient::builder().mock().build()),
             ProverResourceType::Gpu => Self::Gpu(ProverClient::builder().cuda().build()),
-            ProverResourceType::Network(config) => Self::Network(build_network_prover(config)),
-        }
+            ProverResourceType::Network(config) => Self::Network(build_network_prover(config)?),
+        })
     }
 
     pub fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
@@ -35,17 +37,46 @@ impl Prover {
         }
     }
 
+    /// Executes the program, returns the guest output captured along with the
+    /// result.
     pub fn execute(
         &self,
         elf: &[u8],
         input: &SP1Stdin,
-    ) -> Result<(sp1_sdk::SP1PublicValues, sp1_sdk::ExecutionReport), Error> {
-        match self {
-            Self::Cpu(cpu_prover) => cpu_prover.execute(elf, input).run(),
-            Self::Gpu(cuda_prover) => cuda_prover.execute(elf, input).run(),
-            Self::Network(network_prover) => network_prover.execute(elf, input).run(),
+        cycle_limit: Option<u64>,
+    ) -> Result<(SP1PublicValues, ExecutionReport, GuestOutput), Error> {
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let builder = match self {
+            Self::Cpu(cpu_prover) => cpu_prover.execute(elf, input),
+            Self::Gpu(cuda_prover) => cuda_prover.execute(elf, input),
+            Self::Network(network_prover) => network_prover.execute(elf, input),
+        };
+        let builder = match cycle_limit {
+            Some(cycle_limit) => builder.cycle_limit(cycle_limit),
+            None => builder,
+        };
+        let result = builder.stdout(&mut stdout).stderr(&mut stderr).run();
+        let guest_output = GuestOutput::new(&stdout, &stderr);
+        match result {
+            Ok((public_values, report)) => Ok((public_values, report, guest_output)),
+            Err(err) => Err(match err.downcast_ref::<ExecutionError>() {
+                Some(ExecutionError::ExceededCycleLimit(cycle_limit)) => {
+                    CommonError::CycleLimitExceeded {
+                        cycle_limit: *cycle_limit,
+                    }
+                    .into()
+                }
+                // The panic message is written to stderr by the guest.
+                Some(ExecutionError::HaltWithNonZeroExitCode(exit_code)) => Error::GuestPanic {
+                    message: match guest_output.stderr.trim() {
+                        "" => err.to_string(),
+                        stderr => stderr.to_string(),
+                    },
+                    exit_code: *exit_code,
+                },
+                _ => Error::Execute(err),
+            }),
         }
-        .map_err(Error::Execute)
     }
 
     pub fn prove(
@@ -76,7 +107,7 @@ impl Prover {
     }
 }
 
-fn build_network_prover(config: &NetworkProverConfig) -> NetworkProver {
+fn build_network_prover(config: &NetworkProverConfig) -> Result<NetworkProver, Error> {
     let mut builder = ProverClient::builder().network();
     // Check if we have a private key in the config or environment
     if let Some(api_key) = &config.api_key {
@@ -84,9 +115,7 @@ fn build_network_prover(config: &NetworkProverConfig) -> NetworkProver {
     } else if let Ok(private_key) = std::env::var("NETWORK_PRIVATE_KEY") {
         builder = builder.private_key(&private_key);
     } else {
-        panic!(
-            "Network proving requires a private key. Set NETWORK_PRIVATE_KEY environment variable or provide api_key in NetworkProverConfig"
-        );
+        return Err(Error::NetworkPrivateKeyMissing);
     }
     // Set the RPC URL if provided
     if !config.endpoint.is_empty() {
@@ -95,5 +124,5 @@ fn build_network_prover(config: &NetworkProverConfig) -> NetworkProver {
         builder = builder.rpc_url(&rpc_url);
     }
     // Otherwise SP1 SDK will use its default RPC URL
-    builder.build()
+    Ok(builder.build())
 }
@@ -0,0 +1,81 @@
+use crate::zkvm::{Error, NAME, SDK_VERSION, sdk::Prover};
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+use sp1_sdk::{SP1ProofMode, SP1ProofWithPublicValues, SP1VerifyingKey};
+use std::sync::OnceLock;
+use tracing::info;
+
+/// Verifier of SP1 proofs created from [`SP1VerifyingKey`] only.
+///
+/// Creating it is cheap, the CPU prover used to verify is only built on the
+/// first verification, and it's shared by all verifiers in the process.
+pub struct EreSP1Verifier {
+    vk: SP1VerifyingKey,
+}
+
+impl EreSP1Verifier {
+    pub fn new(vk: SP1VerifyingKey) -> Self {
+        Self { vk }
+    }
+}
+
+/// Returns the CPU prover shared by all verifiers, built on the first call.
+fn shared_prover() -> &'static Prover {
+    static PROVER: OnceLock<Prover> = OnceLock::new();
+    PROVER.get_or_init(Prover::default)
+}
+
+impl zkVMVerifier for EreSP1Verifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(shared_prover(), &self.vk, proof)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreSP1Verifier {
+    type ProgramDigest = SP1VerifyingKey;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.vk.clone())
+    }
+}
+
+pub fn verify(
+    prover: &Prover,
+    vk: &SP1VerifyingKey,
+    proof: &Proof,
+) -> Result<PublicValues, zkVMError> {
+    info!("Verifying proof…");
+
+    let proof_kind = proof.kind();
+
+    let (proof, _): (SP1ProofWithPublicValues, _) =
+        bincode::serde::decode_from_slice(proof.as_bytes(), bincode::config::legacy()).map_err(
+            |err| zkVMError::invalid_proof(CommonError::deserialize("proof", "bincode", err)),
+        )?;
+    let inner_proof_kind = SP1ProofMode::from(&proof.proof);
+
+    if !matches!(
+        (proof_kind, inner_proof_kind),
+        (ProofKind::Compressed, SP1ProofMode::Compressed)
+            | (ProofKind::Groth16, SP1ProofMode::Groth16)
+            | (ProofKind::Core, SP1ProofMode::Core)
+            | (ProofKind::Plonk, SP1ProofMode::Plonk)
+    ) {
+        return Err(Error::InvalidProofKind(proof_kind, inner_proof_kind).into());
+    }
+
+    prover.verify(&proof, vk)?;
+
+    let public_values_bytes = proof.public_values.as_slice().to_vec();
+
+    Ok(public_values_bytes)
+}
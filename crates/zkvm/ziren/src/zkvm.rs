@@ -1,13 +1,17 @@
 use crate::program::ZirenProgram;
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    CommonError, ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
+    ProveOptions, ProvePhase, ProverResourceKind, ProverResourceType, PublicValues, peak_memory,
+    reset_peak_memory, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
+};
+use std::{
+    panic,
+    time::{Duration, Instant},
 };
-use std::{panic, time::Instant};
 use tracing::info;
+use zkm_core_executor::ExecutionError;
 use zkm_sdk::{
-    CpuProver, Prover, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey, ZKMStdin,
+    CpuProver, Prover, ZKMContext, ZKMProofKind, ZKMProofWithPublicValues, ZKMProvingKey, ZKMStdin,
     ZKMVerifyingKey,
 };
 
@@ -19,49 +23,81 @@ include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
 pub struct EreZiren {
     program: ZirenProgram,
+    resource: ProverResourceType,
     pk: ZKMProvingKey,
     vk: ZKMVerifyingKey,
+    setup_time: Duration,
 }
 
 impl EreZiren {
     pub fn new(program: ZirenProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if matches!(
+        Self::check_prover_resource(&resource)?;
+        let start = Instant::now();
+        let (pk, vk) = cpu_prover(&resource).setup(program.elf());
+        let setup_time = start.elapsed();
+        Ok(Self {
+            program,
             resource,
-            ProverResourceType::Gpu | ProverResourceType::Network(_)
-        ) {
-            panic!("Network or Gpu proving not yet implemented for ZKM. Use CPU resource type.");
-        }
-        let (pk, vk) = CpuProver::new().setup(program.elf());
-        Ok(Self { program, pk, vk })
+            pk,
+            vk,
+            setup_time,
+        })
     }
 }
 
 impl zkVM for EreZiren {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let mut stdin = ZKMStdin::new();
         stdin.write_slice(input);
 
+        let mut context = ZKMContext::builder();
+        if let Some(cycle_limit) = options.cycle_limit {
+            context.max_cycles(cycle_limit);
+        }
+
         let start = Instant::now();
-        let (public_inputs, exec_report) = CpuProver::new()
-            .execute(self.program.elf(), &stdin)
-            .map_err(Error::Execute)?;
+        let (public_inputs, exec_report) = cpu_prover(&self.resource)
+            .zkm_prover()
+            .execute(self.program.elf(), &stdin, context.build())
+            .map_err(|err| match err {
+                ExecutionError::ExceededCycleLimit(cycle_limit) => {
+                    CommonError::CycleLimitExceeded { cycle_limit }.into()
+                }
+                err => Error::Execute(err.into()),
+            })?;
         let execution_duration = start.elapsed();
 
+        let total_num_cycles = exec_report.total_instruction_count();
+        options.check_cycles(total_num_cycles)?;
+
         Ok((
             public_inputs.to_vec(),
             ProgramExecutionReport {
-                total_num_cycles: exec_report.total_instruction_count(),
+                total_num_cycles,
                 region_cycles: exec_report.cycle_tracker.into_iter().collect(),
                 execution_duration,
+                guest_output: None,
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        // Execute first to enforce the cycle limit, since the prover doesn't
+        // support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
+        }
+
         info!("Generating proof…");
 
         let mut stdin = ZKMStdin::new();
@@ -70,13 +106,18 @@ impl zkVM for EreZiren {
         let inner_proof_kind = match proof_kind {
             ProofKind::Compressed => ZKMProofKind::Compressed,
             ProofKind::Groth16 => ZKMProofKind::Groth16,
+            ProofKind::Core => ZKMProofKind::Core,
+            ProofKind::Plonk => ZKMProofKind::Plonk,
         };
 
-        let start = std::time::Instant::now();
-        let proof =
-            panic::catch_unwind(|| CpuProver::new().prove(&self.pk, stdin, inner_proof_kind))
-                .map_err(|err| Error::ProvePanic(panic_msg(err)))?
-                .map_err(Error::Prove)?;
+        options.report_progress(ProvePhase::Prove);
+        reset_peak_memory();
+        let start = Instant::now();
+        let proof = panic::catch_unwind(|| {
+            cpu_prover(&self.resource).prove(&self.pk, stdin, inner_proof_kind)
+        })
+        .map_err(|err| Error::ProvePanic(panic_msg(err)))?
+        .map_err(Error::Prove)?;
         let proving_time = start.elapsed();
 
         let public_values = proof.public_values.to_vec();
@@ -86,32 +127,37 @@ impl zkVM for EreZiren {
                 .map_err(|err| CommonError::serialize("proof", "bincode", err))?,
         );
 
-        Ok((
-            public_values,
-            proof,
-            ProgramProvingReport::new(proving_time),
-        ))
+        let report = ProgramProvingReport::new(proving_time)
+            .with_setup_time(self.setup_time)
+            .with_proof_size(&proof)
+            .with_peak_memory(peak_memory());
+
+        Ok((public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
         info!("Verifying proof…");
 
         let proof_kind = proof.kind();
 
         let (proof, _): (ZKMProofWithPublicValues, _) =
             bincode::serde::decode_from_slice(proof.as_bytes(), bincode::config::legacy())
-                .map_err(|err| CommonError::deserialize("proof", "bincode", err))?;
+                .map_err(|err| {
+                    zkVMError::invalid_proof(CommonError::deserialize("proof", "bincode", err))
+                })?;
         let inner_proof_kind = ZKMProofKind::from(&proof.proof);
 
         if !matches!(
             (proof_kind, inner_proof_kind),
             (ProofKind::Compressed, ZKMProofKind::Compressed)
                 | (ProofKind::Groth16, ZKMProofKind::Groth16)
+                | (ProofKind::Core, ZKMProofKind::Core)
+                | (ProofKind::Plonk, ZKMProofKind::Plonk)
         ) {
-            bail!(Error::InvalidProofKind(proof_kind, inner_proof_kind));
+            return Err(Error::InvalidProofKind(proof_kind, inner_proof_kind).into());
         }
 
-        CpuProver::new()
+        cpu_prover(&self.resource)
             .verify(&proof, &self.vk)
             .map_err(Error::Verify)?;
 
@@ -127,14 +173,36 @@ impl zkVM for EreZiren {
     }
 }
 
+impl zkVMCapabilities for EreZiren {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] =
+        &[ProverResourceKind::Cpu, ProverResourceKind::Mock];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[
+        ProofKind::Compressed,
+        ProofKind::Groth16,
+        ProofKind::Core,
+        ProofKind::Plonk,
+    ];
+}
+
 impl zkVMProgramDigest for EreZiren {
     type ProgramDigest = ZKMVerifyingKey;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.vk.clone())
     }
 }
 
+/// Returns [`CpuProver`] in mock mode if [`ProverResourceType::Mock`] is
+/// selected, which creates and only accepts mock proofs.
+fn cpu_prover(resource: &ProverResourceType) -> CpuProver {
+    if resource.is_mock() {
+        CpuProver::mock()
+    } else {
+        CpuProver::new()
+    }
+}
+
 fn panic_msg(err: Box<dyn std::any::Any + Send + 'static>) -> String {
     None.or_else(|| err.downcast_ref::<String>().cloned())
         .or_else(|| err.downcast_ref::<&'static str>().map(ToString::to_string))
@@ -145,7 +213,11 @@ fn panic_msg(err: Box<dyn std::any::Any + Send + 'static>) -> String {
 mod tests {
     use crate::{compiler::RustMips32r2Customized, program::ZirenProgram, zkvm::EreZiren};
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_mock, run_zkvm_prove_with_proof_kind, run_zkvm_region_cycles,
+            testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
@@ -174,6 +246,15 @@ mod tests {
         run_zkvm_execute(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_region_cycles() {
+        let program = basic_program();
+        let zkvm = EreZiren::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_region_cycles(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_execute_invalid_input() {
         let program = basic_program();
@@ -184,6 +265,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreZiren::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -193,6 +283,25 @@ mod tests {
         run_zkvm_prove(&zkvm, &test_case);
     }
 
+    #[test]
+    fn test_prove_core() {
+        let program = basic_program();
+        let zkvm = EreZiren::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_with_proof_kind(&zkvm, &test_case, ProofKind::Core);
+    }
+
+    #[test]
+    fn test_prove_mock() {
+        let program = basic_program();
+        let mock_zkvm = EreZiren::new(program.clone(), ProverResourceType::Mock).unwrap();
+        let zkvm = EreZiren::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_prove_mock(&mock_zkvm, &zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove_invalid_input() {
         let program = basic_program();
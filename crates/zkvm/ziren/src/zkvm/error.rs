@@ -1,9 +1,12 @@
-use ere_zkvm_interface::zkvm::ProofKind;
+use ere_zkvm_interface::zkvm::{CommonError, ProofKind, zkVMError};
 use thiserror::Error;
 use zkm_sdk::{ZKMProofKind, ZKMVerificationError};
 
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error(transparent)]
+    CommonError(#[from] CommonError),
+
     // Execute
     #[error("Ziren execution failed: {0}")]
     Execute(#[source] anyhow::Error),
@@ -22,3 +25,14 @@ pub enum Error {
     #[error("Ziren verification failed: {0}")]
     Verify(#[source] ZKMVerificationError),
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(_) => zkVMError::guest_panic(err, None),
+            Error::InvalidProofKind(..) | Error::Verify(_) => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
     program::ZiskProgram,
-    zkvm::sdk::{RomDigest, ZiskOptions, ZiskSdk, ZiskServer},
+    zkvm::sdk::{ZiskOptions, ZiskSdk, ZiskServer},
 };
-use anyhow::bail;
 use ere_zkvm_interface::zkvm::{
-    CommonError, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind,
-    ProverResourceType, PublicValues, zkVM, zkVMProgramDigest,
+    ExecuteOptions, ProgramExecutionReport, ProgramProvingReport, Proof, ProofKind, ProveOptions,
+    ProvePhase, ProverResourceKind, ProverResourceType, PublicValues, mock_prove, mock_verify,
+    region_cycles, zkVM, zkVMCapabilities, zkVMError, zkVMProgramDigest,
 };
 use std::{
     sync::{Mutex, MutexGuard},
@@ -14,8 +14,11 @@ use std::{
 
 mod error;
 mod sdk;
+mod verifier;
 
 pub use error::Error;
+pub use sdk::RomDigest;
+pub use verifier::EreZiskVerifier;
 
 include!(concat!(env!("OUT_DIR"), "/name_and_sdk_version.rs"));
 
@@ -30,9 +33,7 @@ pub struct EreZisk {
 
 impl EreZisk {
     pub fn new(program: ZiskProgram, resource: ProverResourceType) -> Result<Self, Error> {
-        if matches!(resource, ProverResourceType::Network(_)) {
-            panic!("Network proving not yet implemented for ZisK. Use CPU or GPU resource type.");
-        }
+        Self::check_prover_resource(&resource)?;
         let sdk = ZiskSdk::new(program.elf, resource, ZiskOptions::from_env())?;
         Ok(Self {
             sdk,
@@ -62,56 +63,71 @@ impl EreZisk {
 }
 
 impl zkVM for EreZisk {
-    fn execute(&self, input: &[u8]) -> anyhow::Result<(PublicValues, ProgramExecutionReport)> {
+    fn execute_with_options(
+        &self,
+        input: &[u8],
+        options: &ExecuteOptions,
+    ) -> Result<(PublicValues, ProgramExecutionReport), zkVMError> {
         let start = Instant::now();
-        let (public_values, total_num_cycles) = self.sdk.execute(input)?;
+        let (public_values, total_num_cycles, guest_output) =
+            self.sdk.execute(input, options.cycle_limit)?;
         let execution_duration = start.elapsed();
 
+        options.check_cycles(total_num_cycles)?;
+
         Ok((
             public_values,
             ProgramExecutionReport {
                 total_num_cycles,
+                region_cycles: region_cycles(&guest_output.stdout),
                 execution_duration,
-                ..Default::default()
+                guest_output: Some(guest_output),
             },
         ))
     }
 
-    fn prove(
+    fn prove_with_options(
         &self,
         input: &[u8],
         proof_kind: ProofKind,
-    ) -> anyhow::Result<(PublicValues, Proof, ProgramProvingReport)> {
-        if proof_kind != ProofKind::Compressed {
-            bail!(CommonError::unsupported_proof_kind(
-                proof_kind,
-                [ProofKind::Compressed]
-            ))
+        options: &ProveOptions,
+    ) -> Result<(PublicValues, Proof, ProgramProvingReport), zkVMError> {
+        Self::check_proof_kind(proof_kind)?;
+
+        if self.sdk.resource().is_mock() {
+            return mock_prove(self, input, proof_kind, options);
         }
 
+        let cancellation = options.cancellation();
+
+        // Execute first to enforce the cycle limit by `ziskemu`, since the
+        // prover server doesn't support it.
+        if options.cycle_limit.is_some() {
+            options.report_progress(ProvePhase::Execute);
+            self.execute_with_options(input, &options.execute_options())?;
+        }
+
+        options.report_progress(ProvePhase::Setup);
         let mut server = self.server()?;
         let server = server.as_mut().expect("server initialized");
 
+        options.report_progress(ProvePhase::Prove);
         let start = Instant::now();
-        let (public_values, proof) = server.prove(input)?;
+        let (public_values, proof) = server.prove(input, &cancellation)?;
         let proving_time = start.elapsed();
 
-        Ok((
-            public_values,
-            Proof::Compressed(proof),
-            ProgramProvingReport::new(proving_time),
-        ))
+        let proof = Proof::Compressed(proof);
+        let report = ProgramProvingReport::new(proving_time).with_proof_size(&proof);
+
+        Ok((public_values, proof, report))
     }
 
-    fn verify(&self, proof: &Proof) -> anyhow::Result<PublicValues> {
-        let Proof::Compressed(proof) = proof else {
-            bail!(CommonError::unsupported_proof_kind(
-                proof.kind(),
-                [ProofKind::Compressed]
-            ))
-        };
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        if self.sdk.resource().is_mock() {
+            return mock_verify(proof);
+        }
 
-        Ok(self.sdk.verify(proof)?)
+        verifier::verify(self.sdk.rom_digest()?, proof)
     }
 
     fn name(&self) -> &'static str {
@@ -123,24 +139,41 @@ impl zkVM for EreZisk {
     }
 }
 
+impl zkVMCapabilities for EreZisk {
+    const SUPPORTED_PROVER_RESOURCE_KINDS: &'static [ProverResourceKind] = &[
+        ProverResourceKind::Cpu,
+        ProverResourceKind::Gpu,
+        ProverResourceKind::Mock,
+    ];
+
+    const SUPPORTED_PROOF_KINDS: &'static [ProofKind] = &[ProofKind::Compressed];
+}
+
 impl zkVMProgramDigest for EreZisk {
     type ProgramDigest = RomDigest;
 
-    fn program_digest(&self) -> anyhow::Result<Self::ProgramDigest> {
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
         Ok(self.sdk.rom_digest()?)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{compiler::RustRv64imaCustomized, program::ZiskProgram, zkvm::EreZisk};
+    use crate::{
+        compiler::RustRv64imaCustomized,
+        program::ZiskProgram,
+        zkvm::{EreZisk, EreZiskVerifier},
+    };
     use ere_test_utils::{
-        host::{TestCase, run_zkvm_execute, run_zkvm_prove, testing_guest_directory},
+        host::{
+            TestCase, run_zkvm_cycle_limit_exceeded, run_zkvm_execute, run_zkvm_prove,
+            run_zkvm_prove_with_verifier, run_zkvm_region_cycles, testing_guest_directory,
+        },
         program::basic::BasicProgramInput,
     };
     use ere_zkvm_interface::{
         compiler::Compiler,
-        zkvm::{ProofKind, ProverResourceType, zkVM},
+        zkvm::{ProofKind, ProverResourceType, zkVM, zkVMProgramDigest},
     };
     use std::sync::{Mutex, OnceLock};
 
@@ -178,6 +211,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_region_cycles() {
+        let program = basic_program();
+        let zkvm = EreZisk::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid();
+        run_zkvm_region_cycles(&zkvm, &test_case);
+    }
+
+    #[test]
+    fn test_cycle_limit() {
+        let program = basic_program();
+        let zkvm = EreZisk::new(program, ProverResourceType::Cpu).unwrap();
+
+        let test_case = BasicProgramInput::valid().into_output_sha256();
+        run_zkvm_cycle_limit_exceeded(&zkvm, &test_case);
+    }
+
     #[test]
     fn test_prove() {
         let program = basic_program();
@@ -200,4 +251,16 @@ mod tests {
             zkvm.prove(&input, ProofKind::default()).unwrap_err();
         }
     }
+
+    #[test]
+    fn test_prove_with_verifier() {
+        let program = basic_program();
+        let zkvm = EreZisk::new(program, ProverResourceType::Cpu).unwrap();
+        let verifier = EreZiskVerifier::new(zkvm.program_digest().unwrap());
+
+        let _guard = PROVE_LOCK.lock().unwrap();
+
+        let test_case = BasicProgramInput::valid().into_output_sha256();
+        run_zkvm_prove_with_verifier(&zkvm, &verifier, &test_case);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::zkvm::sdk::RomDigest;
 use bytemuck::PodCastError;
-use ere_zkvm_interface::zkvm::CommonError;
+use ere_zkvm_interface::zkvm::{CommonError, zkVMError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,6 +9,9 @@ pub enum Error {
     CommonError(#[from] CommonError),
 
     // Execution
+    #[error("Zisk execution failed: {0}")]
+    Execute(#[source] CommonError),
+
     #[error("Total steps not found in execution report")]
     TotalStepsNotFound,
 
@@ -48,3 +51,21 @@ pub enum Error {
         proved: RomDigest,
     },
 }
+
+impl From<Error> for zkVMError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::CommonError(err) => err.into(),
+            Error::Execute(ref inner) => {
+                let exit_code = inner.exit_code();
+                zkVMError::guest_panic(err, exit_code)
+            }
+            Error::InvalidProof(_)
+            | Error::CastProofBytesToU64s(_)
+            | Error::InvalidPublicValue
+            | Error::InvalidPublicValuesLength(_)
+            | Error::UnexpectedRomDigest { .. } => zkVMError::invalid_proof(err),
+            _ => zkVMError::infrastructure(err),
+        }
+    }
+}
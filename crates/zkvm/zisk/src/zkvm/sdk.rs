@@ -1,5 +1,7 @@
 use crate::zkvm::Error;
-use ere_zkvm_interface::zkvm::{CommonError, ProverResourceType, PublicValues};
+use ere_zkvm_interface::zkvm::{
+    Cancellation, CommonError, GuestOutput, ProverResourceKind, ProverResourceType, PublicValues,
+};
 use std::{
     collections::BTreeMap,
     env, fs,
@@ -170,7 +172,16 @@ impl ZiskSdk {
     }
 
     /// Execute the ELF with the given `input`.
-    pub fn execute(&self, input: &[u8]) -> Result<(PublicValues, u64), Error> {
+    /// Executes by `ziskemu`, returns the public values, total cycles and the
+    /// `ziskemu` output, which contains the guest output.
+    ///
+    /// If `cycle_limit` is given, `ziskemu` stops right after exceeding it and
+    /// [`CommonError::CycleLimitExceeded`] is returned.
+    pub fn execute(
+        &self,
+        input: &[u8],
+        cycle_limit: Option<u64>,
+    ) -> Result<(PublicValues, u64, GuestOutput), Error> {
         let tempdir = tempdir().map_err(CommonError::tempdir)?;
         let input_path = tempdir.path().join("input");
         let output_path = tempdir.path().join("output");
@@ -179,25 +190,23 @@ impl ZiskSdk {
             .map_err(|err| CommonError::write_file("input", &input_path, err))?;
 
         let mut cmd = Command::new("ziskemu");
-        let output = cmd
-            .arg("--elf")
+        cmd.arg("--elf")
             .arg(&self.elf_path)
             .arg("--inputs")
             .arg(input_path)
             .arg("--output")
             .arg(&output_path)
-            .arg("--stats") // Enable stats in order to get total steps.
+            .arg("--stats"); // Enable stats in order to get total steps.
+        if let Some(cycle_limit) = cycle_limit {
+            // Allow one more step to tell exceeding from finishing exactly
+            // at the limit.
+            cmd.arg("--max-steps")
+                .arg(cycle_limit.saturating_add(1).to_string());
+        }
+        let output = cmd
             .output()
             .map_err(|err| CommonError::command(&cmd, err))?;
 
-        if !output.status.success() {
-            return Err(CommonError::command_exit_non_zero(
-                &cmd,
-                output.status,
-                Some(&output),
-            ))?;
-        }
-
         // Extract cycle count from the stdout.
 
         let total_num_cycles = String::from_utf8_lossy(&output.stdout)
@@ -207,13 +216,30 @@ impl ZiskSdk {
                     .split_whitespace()
                     .next()
                     .and_then(|steps| steps.parse::<u64>().ok())
-            })
-            .ok_or(Error::TotalStepsNotFound)?;
+            });
+
+        if let (Some(cycle_limit), Some(total_num_cycles)) = (cycle_limit, total_num_cycles) {
+            if total_num_cycles > cycle_limit {
+                return Err(CommonError::CycleLimitExceeded { cycle_limit }.into());
+            }
+        }
+
+        if !output.status.success() {
+            return Err(Error::Execute(CommonError::command_exit_non_zero(
+                &cmd,
+                output.status,
+                Some(&output),
+            )));
+        }
+
+        let total_num_cycles = total_num_cycles.ok_or(Error::TotalStepsNotFound)?;
 
         let public_values = fs::read(&output_path)
             .map_err(|err| CommonError::read_file("output", &output_path, err))?;
 
-        Ok((public_values, total_num_cycles))
+        let guest_output = GuestOutput::new(&output.stdout, &output.stderr);
+
+        Ok((public_values, total_num_cycles, guest_output))
     }
 
     /// Returns the ROM digest of the ELF.
@@ -233,6 +259,11 @@ impl ZiskSdk {
         rom_digest.ok_or(Error::RomSetupFailedBefore)
     }
 
+    /// Returns the [`ProverResourceType`] the SDK is created with.
+    pub fn resource(&self) -> &ProverResourceType {
+        &self.resource
+    }
+
     /// Start a server of the ELF.
     pub fn server(&self) -> Result<ZiskServer, Error> {
         // Setup ROM and get ROM digest if it's not done yet.
@@ -248,10 +279,11 @@ impl ZiskSdk {
                         .join("libzisk_witness_cuda.so"),
                 ),
             ),
-            ProverResourceType::Network(_) => {
-                panic!(
-                    "Network proving not yet implemented for ZisK. Use CPU or GPU resource type."
-                );
+            ProverResourceType::Network(_) | ProverResourceType::Mock => {
+                Err(CommonError::unsupported_prover_resource_kind(
+                    self.resource.kind(),
+                    [ProverResourceKind::Cpu, ProverResourceKind::Gpu],
+                ))?
             }
         };
 
@@ -276,47 +308,45 @@ impl ZiskSdk {
 
         Ok(server)
     }
+}
 
-    /// Verify the proof of the ELF, and returns public values.
-    pub fn verify(&self, proof: &[u8]) -> Result<PublicValues, Error> {
-        let rom_digest = self.rom_digest()?;
-
-        let tempdir = tempdir().map_err(CommonError::tempdir)?;
-        let proof_path = tempdir.path().join("proof");
-
-        fs::write(&proof_path, proof)
-            .map_err(|err| CommonError::write_file("proof", &proof_path, err))?;
+/// Verify the proof of the ELF with `rom_digest`, and returns public values.
+pub fn verify(rom_digest: RomDigest, proof: &[u8]) -> Result<PublicValues, Error> {
+    let tempdir = tempdir().map_err(CommonError::tempdir)?;
+    let proof_path = tempdir.path().join("proof");
 
-        let mut cmd = Command::new("cargo-zisk");
-        let output = cmd
-            .arg("verify")
-            .arg("--proof")
-            .arg(&proof_path)
-            .output()
-            .map_err(|err| CommonError::command(&cmd, err))?;
+    fs::write(&proof_path, proof)
+        .map_err(|err| CommonError::write_file("proof", &proof_path, err))?;
 
-        if !output.status.success() {
-            Err(Error::InvalidProof(
-                String::from_utf8_lossy(&output.stderr).to_string(),
-            ))?
-        }
+    let mut cmd = Command::new("cargo-zisk");
+    let output = cmd
+        .arg("verify")
+        .arg("--proof")
+        .arg(&proof_path)
+        .output()
+        .map_err(|err| CommonError::command(&cmd, err))?;
 
-        let proof = fs::read(&proof_path)
-            .map_err(|err| CommonError::read_file("proof", &proof_path, err))?;
+    if !output.status.success() {
+        Err(Error::InvalidProof(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ))?
+    }
 
-        // Deserialize public values.
-        let (proved_rom_digest, public_values) = deserialize_public_values(&proof)?;
+    let proof =
+        fs::read(&proof_path).map_err(|err| CommonError::read_file("proof", &proof_path, err))?;
 
-        // The proved ROM digest should be equal to preprocessed one.
-        if proved_rom_digest != rom_digest {
-            return Err(Error::UnexpectedRomDigest {
-                preprocessed: rom_digest,
-                proved: proved_rom_digest,
-            });
-        }
+    // Deserialize public values.
+    let (proved_rom_digest, public_values) = deserialize_public_values(&proof)?;
 
-        Ok(public_values)
+    // The proved ROM digest should be equal to preprocessed one.
+    if proved_rom_digest != rom_digest {
+        return Err(Error::UnexpectedRomDigest {
+            preprocessed: rom_digest,
+            proved: proved_rom_digest,
+        });
     }
+
+    Ok(public_values)
 }
 
 /// ZisK server status returned from `cargo-zisk prove-client status`.
@@ -377,7 +407,14 @@ impl ZiskServer {
     }
 
     /// Send prove request to server and wait for proof to be created.
-    pub fn prove(&mut self, input: &[u8]) -> Result<(PublicValues, Vec<u8>), Error> {
+    ///
+    /// If the proving is cancelled, the server is killed as well since it
+    /// keeps proving after the client exits, and it should be recreated.
+    pub fn prove(
+        &mut self,
+        input: &[u8],
+        cancellation: &Cancellation,
+    ) -> Result<(PublicValues, Vec<u8>), Error> {
         // Prefix that ZisK server will add to the file name of the proof.
         // We use constant because the file will be save to a temporary dir,
         // so there will be no conflict.
@@ -394,17 +431,19 @@ impl ZiskServer {
         // NOTE: Use snake case for `prove-client` command
         // Issue for tracking: https://github.com/eth-act/ere/issues/151.
         let mut cmd = Command::new("cargo-zisk");
-        let output = cmd
-            .args(["prove-client", "prove"])
+        cmd.args(["prove-client", "prove"])
             .arg("--input")
             .arg(input_path)
             .arg("--output_dir")
             .arg(&output_path)
             .args(["-p", PREFIX])
             .args(["--aggregation", "--verify_proofs"])
-            .args(self.options.prove_args())
-            .output()
-            .map_err(|err| CommonError::command(&cmd, err))?;
+            .args(self.options.prove_args());
+        let output = cancellation.output(&mut cmd).inspect_err(|err| {
+            if matches!(err, CommonError::Cancelled | CommonError::Timeout { .. }) {
+                self.kill();
+            }
+        })?;
 
         if !output.status.success() {
             return Err(CommonError::command_exit_non_zero(
@@ -436,6 +475,15 @@ impl ZiskServer {
         Ok((public_values, proof))
     }
 
+    /// Kill the server, which makes the following `status` fail.
+    fn kill(&mut self) {
+        self.child
+            .kill()
+            .and_then(|_| self.child.wait())
+            .map(|_| ())
+            .unwrap_or_else(|err| error!("Failed to kill server: {err:?}"));
+    }
+
     /// Wait until the server status to be idle.
     fn wait_until_ready(&self) -> Result<(), Error> {
         const TIMEOUT: Duration = Duration::from_secs(300); // 5mins
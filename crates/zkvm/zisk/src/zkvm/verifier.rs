@@ -0,0 +1,47 @@
+use crate::zkvm::{NAME, SDK_VERSION, sdk::RomDigest};
+use ere_zkvm_interface::zkvm::{
+    CommonError, Proof, ProofKind, PublicValues, zkVMError, zkVMProgramDigest, zkVMVerifier,
+};
+
+/// Verifier of ZisK proofs created from [`RomDigest`] only.
+pub struct EreZiskVerifier {
+    rom_digest: RomDigest,
+}
+
+impl EreZiskVerifier {
+    pub fn new(rom_digest: RomDigest) -> Self {
+        Self { rom_digest }
+    }
+}
+
+impl zkVMVerifier for EreZiskVerifier {
+    fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        verify(self.rom_digest, proof)
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sdk_version(&self) -> &'static str {
+        SDK_VERSION
+    }
+}
+
+impl zkVMProgramDigest for EreZiskVerifier {
+    type ProgramDigest = RomDigest;
+
+    fn program_digest(&self) -> Result<Self::ProgramDigest, zkVMError> {
+        Ok(self.rom_digest)
+    }
+}
+
+pub fn verify(rom_digest: RomDigest, proof: &Proof) -> Result<PublicValues, zkVMError> {
+    let Proof::Compressed(proof) = proof else {
+        return Err(
+            CommonError::unsupported_proof_kind(proof.kind(), [ProofKind::Compressed]).into(),
+        );
+    };
+
+    Ok(crate::zkvm::sdk::verify(rom_digest, proof)?)
+}
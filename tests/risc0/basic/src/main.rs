@@ -17,6 +17,14 @@ impl Platform for Risc0Platform {
     fn write_output(output: &[u8]) {
         env::commit_slice(output);
     }
+
+    fn cycle_count() -> Option<u64> {
+        Some(env::cycle_count())
+    }
+
+    fn print(line: &str) {
+        println!("{line}");
+    }
 }
 
 fn main() {
@@ -17,6 +17,10 @@ impl Platform for SP1Platform {
     fn write_output(output: &[u8]) {
         sp1_zkvm::io::commit_slice(output);
     }
+
+    fn print(line: &str) {
+        println!("{line}");
+    }
 }
 
 pub fn main() {
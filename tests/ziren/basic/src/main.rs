@@ -17,6 +17,10 @@ impl Platform for ZirenPlatform {
     fn write_output(output: &[u8]) {
         zkm_zkvm::io::commit_slice(output);
     }
+
+    fn print(line: &str) {
+        println!("{line}");
+    }
 }
 
 pub fn main() {
@@ -1,5 +1,6 @@
 #![no_main]
 
+use core::arch::asm;
 use ere_test_utils::{
     guest::{Digest, Platform, Sha256},
     program::{basic::BasicProgram, Program},
@@ -20,6 +21,18 @@ impl Platform for ZiskPlatform {
             ziskos::set_output(idx, u32::from_le_bytes(bytes.try_into().unwrap()))
         });
     }
+
+    fn cycle_count() -> Option<u64> {
+        let cycles: u64;
+        // `rdcycle`, encoded by `.insn` since the target doesn't enable the
+        // `Zicntr` extension for the assembler.
+        unsafe { asm!(".insn i 0x73, 2, {0}, x0, -1024", out(reg) cycles) };
+        Some(cycles)
+    }
+
+    fn print(line: &str) {
+        println!("{line}");
+    }
 }
 
 fn main() {